@@ -1,16 +1,24 @@
 use proc_macro::TokenStream;
 
-use darling::FromField;
+use darling::{ast::NestedMeta, FromDeriveInput, FromField, FromMeta};
 use quote::quote;
-use syn::{Data, DataStruct, Fields, Ident};
+use regex::Regex;
+use syn::{
+    ext::IdentExt, parse_macro_input, Attribute, Data, DataEnum, DataStruct, DataUnion, DeriveInput, Expr,
+    ExprLit, Field, Fields, GenericArgument, Lit, LitStr, Meta, MetaNameValue, Path, PathArguments,
+    PathSegment, Token, Type,
+};
 
 #[proc_macro_derive(Config, attributes(config))]
 pub fn derive_config(input: TokenStream) -> TokenStream {
-    let ast = syn::parse(input).unwrap();
+    let ast = parse_macro_input!(input as DeriveInput);
+
     expand_derive_config(&ast)
+        .unwrap_or_else(darling::Error::write_errors)
+        .into()
 }
 
-fn expand_derive_config(ast: &syn::DeriveInput) -> TokenStream {
+fn expand_derive_config(ast: &DeriveInput) -> darling::Result<proc_macro2::TokenStream> {
     let name = &ast.ident;
 
     let fields = match &ast.data {
@@ -19,35 +27,559 @@ fn expand_derive_config(ast: &syn::DeriveInput) -> TokenStream {
             ..
         }) => &fields.named,
 
-        _ => panic!("expected a struct with named fields"),
+        Data::Struct(DataStruct { fields, .. }) => {
+            return Err(darling::Error::custom("expected a struct with named fields").with_span(fields))
+        }
+
+        Data::Enum(DataEnum { enum_token, .. }) => {
+            return Err(darling::Error::custom("expected a struct with named fields").with_span(enum_token))
+        }
+
+        Data::Union(DataUnion { union_token, .. }) => {
+            return Err(darling::Error::custom("expected a struct with named fields").with_span(union_token))
+        }
     };
 
-    let (field_name, field_default): (Vec<&Ident>, Vec<String>) = fields
+    let mut errors = darling::Error::accumulator();
+
+    let container = errors
+        .handle(ContainerArgs::from_derive_input(ast))
+        .unwrap_or_default();
+    let rename_all = container
+        .rename_all
+        .as_ref()
+        .and_then(|v| errors.handle(RenameRule::from_str(&v.value()).map_err(|err| err.with_span(v))))
+        .unwrap_or(RenameRule::ScreamingSnake);
+
+    let mut field_metas = Vec::new();
+    let mut field_checks = Vec::new();
+
+    for field in fields {
+        let args = match errors.handle(DeriveArgs::from_field(field).and_then(|v| v.check(field))) {
+            Some(args) => args,
+            None => continue,
+        };
+
+        let field_name = field.ident.as_ref().expect("named field");
+        let field_key = field_name.unraw().to_string();
+
+        let ty = &field.ty;
+
+        let field_default = match (&args.default, &args.default_fn) {
+            (Some(default), _) => default_value(default, ty),
+
+            (None, Some(default_fn)) => quote! {
+                Some(|| {
+                    let value: #ty = #default_fn();
+                    config::__internal::to_value(&value)
+                })
+            },
+
+            (None, None) => quote! { None },
+        };
+
+        let field_profiles = args.profile.iter().flat_map(|v| &v.0).map(|(profile, default)| {
+            let default = default_value(default, ty);
+            quote! { (#profile, #default) }
+        });
+
+        let field_check = if args.nested || has_serde_with(&field.attrs) {
+            quote! { None }
+        } else {
+            quote! { Some(|value| value.try_deserialize::<#ty>().map(|_| ())) }
+        };
+
+        let field_nested = if args.nested {
+            quote! { Some(<#ty as ConfigTrait>::fields()) }
+        } else {
+            quote! { None }
+        };
+
+        let field_secret = is_secret(ty);
+
+        let field_ty = quote!(#ty).to_string().replace(' ', "");
+        let field_doc = doc(&args.attrs);
+
+        let field_env_key = rename_all.apply(&field_key);
+        let field_env = match &args.env {
+            Some(env) => quote! { Some(#env) },
+            None => quote! { None },
+        };
+        let field_aliases = &args.alias;
+        let field_list_separator = match &args.list_separator {
+            Some(separator) => quote! { Some(#separator) },
+            None => quote! { None },
+        };
+        let field_map = args.map;
+        let field_kind = field_kind(ty, args.map, args.list_separator.is_some());
+
+        field_metas.push(quote! {
+            config::Field {
+                name: #field_key,
+                ty: #field_ty,
+                doc: #field_doc,
+                default: #field_default,
+                profiles: vec![#(#field_profiles),*],
+                check: #field_check,
+                env_key: #field_env_key,
+                env: #field_env,
+                aliases: vec![#(#field_aliases),*],
+                list_separator: #field_list_separator,
+                map: #field_map,
+                kind: #field_kind,
+                secret: #field_secret,
+                nested: #field_nested,
+            }
+        });
+
+        if args.nested {
+            field_checks.push(quote! {
+                violations.nested(#field_key, |violations| {
+                    ConfigTrait::validate(&self.#field_name, violations)
+                });
+            });
+        }
+
+        if let Some(RangeArgs { min, max }) = &args.range {
+            let min = bound(min);
+            let max = bound(max);
+
+            field_checks.push(quote! {
+                violations.check(
+                    #field_key,
+                    config::validate::range(&self.#field_name, #min, #max),
+                );
+            });
+        }
+
+        if let Some(OneOfArgs(values)) = &args.one_of {
+            field_checks.push(quote! {
+                violations.check(
+                    #field_key,
+                    config::validate::one_of(&self.#field_name, &[#(#values),*]),
+                );
+            });
+        }
+
+        if let Some(pattern) = &args.regex {
+            field_checks.push(quote! {
+                violations.check(
+                    #field_key,
+                    config::validate::regex(&self.#field_name, #pattern),
+                );
+            });
+        }
+
+        if args.non_empty {
+            field_checks.push(quote! {
+                violations.check(
+                    #field_key,
+                    config::validate::non_empty(&self.#field_name),
+                );
+            });
+        }
+
+        if let Some(validate) = &args.validate {
+            field_checks.push(quote! {
+                violations.check(#field_key, #validate(&self.#field_name));
+            });
+        }
+    }
+
+    errors.finish()?;
+
+    Ok(quote! {
+        impl ConfigTrait for #name {
+            fn fields() -> Vec<config::Field> {
+                vec![#(#field_metas),*]
+            }
+
+            #[allow(unused_variables)]
+            fn validate(&self, violations: &mut config::validate::Violations) {
+                #(#field_checks)*
+            }
+        }
+    })
+}
+
+fn doc(attrs: &[Attribute]) -> String {
+    attrs
         .iter()
-        .filter_map(|field| match DeriveArgs::from_field(field).ok()?.default? {
-            default if !default.is_empty() => Some((field.ident.as_ref()?, default)),
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value: Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }),
+                ..
+            }) => Some(lit.value()),
             _ => None,
         })
-        .unzip();
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
 
-    quote! {
-        impl ConfigTrait for #name {
-            fn from_env(prefix: &str) -> Result<Self, ConfigError> {
-                config::__internal::Config::builder()
-                    #(
-                    .set_default(stringify!(#field_name), #field_default)?
-                    )*
-                    .add_source(config::__internal::Environment::with_prefix(prefix))
-                    .build()?
-                    .try_deserialize()
+/// Whether the field relies on custom `serde` (de)serialization, in which case its value cannot be
+/// checked against the field type alone.
+fn has_serde_with(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("serde"))
+        .any(|attr| {
+            let mut found = false;
+
+            let _ = attr.parse_nested_meta(|meta| {
+                found |= ["with", "deserialize_with", "flatten"]
+                    .iter()
+                    .any(|v| meta.path.is_ident(v));
+
+                if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                }
+                Ok(())
+            });
+
+            found
+        })
+}
+
+/// Returns the kind of a field type, `map` and `list` being set when the field is marked as such
+/// (e.g. for type aliases).
+fn field_kind(ty: &Type, map: bool, list: bool) -> proc_macro2::TokenStream {
+    let ty = unwrap_option(ty);
+    let segment = last_segment(ty);
+    let name = segment.map(|v| v.ident.to_string()).unwrap_or_default();
+    let args = segment.map(type_args).unwrap_or_default();
+
+    if map || ["HashMap", "BTreeMap", "IndexMap"].contains(&name.as_str()) {
+        let scalar = scalar_kind(args.get(1).copied());
+        quote! { config::FieldKind::Map(#scalar) }
+    } else if list
+        || ["Vec", "VecDeque", "LinkedList", "HashSet", "BTreeSet", "IndexSet"].contains(&name.as_str())
+    {
+        let scalar = scalar_kind(args.first().copied());
+        quote! { config::FieldKind::List(#scalar) }
+    } else {
+        let scalar = scalar_kind(Some(ty));
+        quote! { config::FieldKind::Scalar(#scalar) }
+    }
+}
+
+fn scalar_kind(ty: Option<&Type>) -> proc_macro2::TokenStream {
+    let name = ty
+        .map(unwrap_option)
+        .and_then(last_segment)
+        .map(|v| v.ident.to_string())
+        .unwrap_or_default();
+
+    match name.as_str() {
+        "bool" => quote! { config::ScalarKind::Bool },
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => quote! { config::ScalarKind::Signed },
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => quote! { config::ScalarKind::Unsigned },
+        "f32" | "f64" => quote! { config::ScalarKind::Float },
+        _ => quote! { config::ScalarKind::Other },
+    }
+}
+
+fn unwrap_option(ty: &Type) -> &Type {
+    match last_segment(ty) {
+        Some(segment) if segment.ident == "Option" => {
+            type_args(segment).first().map_or(ty, |v| unwrap_option(v))
+        }
+        _ => ty,
+    }
+}
+
+fn last_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    }
+}
+
+fn type_args(segment: &PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|v| match v {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a field type is `Secret<T>` or `Option<Secret<T>>`, its values being redacted whether or
+/// not the field is marked with `#[config(secret)]`.
+fn is_secret(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+            matches!(args.args.first(), Some(GenericArgument::Type(ty)) if is_secret(ty))
+        }
+        _ => segment.ident == "Secret",
+    }
+}
+
+fn bound(value: &Option<Bound>) -> proc_macro2::TokenStream {
+    match value {
+        Some(Bound(value)) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+#[derive(Default, FromDeriveInput)]
+#[darling(default, attributes(config))]
+struct ContainerArgs {
+    rename_all: Option<LitStr>,
+}
+
+/// Case conversion applied to field names when building environment variable names, following
+/// `serde` naming.
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(value: &str) -> darling::Result<Self> {
+        match value {
+            "lowercase" => Ok(RenameRule::Lower),
+            "UPPERCASE" => Ok(RenameRule::Upper),
+            "PascalCase" => Ok(RenameRule::Pascal),
+            "camelCase" => Ok(RenameRule::Camel),
+            "snake_case" => Ok(RenameRule::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnake),
+            "kebab-case" => Ok(RenameRule::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameRule::ScreamingKebab),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+
+    fn apply(self, name: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => name.to_ascii_lowercase(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => name.to_ascii_uppercase(),
+            RenameRule::Kebab => name.replace('_', "-"),
+            RenameRule::ScreamingKebab => name.replace('_', "-").to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let pascal: String = name
+                    .split('_')
+                    .map(|word| {
+                        let mut chars = word.chars();
+                        chars
+                            .next()
+                            .map(|v| v.to_ascii_uppercase().to_string() + chars.as_str())
+                            .unwrap_or_default()
+                    })
+                    .collect();
+
+                match self {
+                    RenameRule::Camel => {
+                        let mut chars = pascal.chars();
+                        chars
+                            .next()
+                            .map(|v| v.to_ascii_lowercase().to_string() + chars.as_str())
+                            .unwrap_or_default()
+                    }
+                    _ => pascal,
+                }
             }
         }
     }
-    .into()
 }
 
 #[derive(Default, FromField)]
 #[darling(default, attributes(config), forward_attrs(allow, doc, cfg))]
 struct DeriveArgs {
-    default: Option<String>,
+    attrs: Vec<Attribute>,
+    default: Option<DefaultArgs>,
+    default_fn: Option<Path>,
+    profile: Option<ProfileArgs>,
+    env: Option<LitStr>,
+    #[darling(multiple)]
+    alias: Vec<LitStr>,
+    list_separator: Option<LitStr>,
+    map: bool,
+    nested: bool,
+    secret: bool,
+    range: Option<RangeArgs>,
+    one_of: Option<OneOfArgs>,
+    regex: Option<LitStr>,
+    non_empty: bool,
+    validate: Option<Path>,
+}
+
+impl DeriveArgs {
+    fn check(self, field: &Field) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+
+        if let (Some(_), Some(default_fn)) = (&self.default, &self.default_fn) {
+            errors.push(
+                darling::Error::custom("`default` and `default_fn` are mutually exclusive")
+                    .with_span(default_fn),
+            );
+        }
+
+        if self.nested
+            && (self.default.is_some()
+                || self.profile.is_some()
+                || self.env.is_some()
+                || !self.alias.is_empty()
+                || self.list_separator.is_some()
+                || self.map
+                || self.default_fn.is_some()
+                || self.secret
+                || self.range.is_some()
+                || self.one_of.is_some()
+                || self.regex.is_some()
+                || self.non_empty
+                || self.validate.is_some())
+        {
+            errors.push(
+                darling::Error::custom(
+                    "nested fields take their defaults and validation from their own type",
+                )
+                .with_span(&field.ident),
+            );
+        }
+
+        if self.secret && !is_secret(&field.ty) {
+            errors.push(
+                darling::Error::custom("secret fields must be wrapped in `config::Secret`")
+                    .with_span(&field.ty),
+            );
+        }
+
+        if let Some(separator) = self.list_separator.as_ref().filter(|v| v.value().is_empty()) {
+            errors.push(darling::Error::custom("list separator must not be empty").with_span(separator));
+        }
+
+        if let Some(pattern) = &self.regex {
+            if let Err(err) = Regex::new(&pattern.value()) {
+                errors.push(darling::Error::custom(format!("invalid pattern: {}", err)).with_span(pattern));
+            }
+        }
+
+        errors.finish_with(self)
+    }
+}
+
+/// Field default value: string literals are kept as raw values (parsed the same way environment
+/// variables are), other expressions are type-checked against the field type.
+enum DefaultArgs {
+    Raw(LitStr),
+    Expr(Expr),
+    Trait,
+}
+
+impl FromMeta for DefaultArgs {
+    fn from_word() -> darling::Result<Self> {
+        Ok(DefaultArgs::Trait)
+    }
+
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Ok(DefaultArgs::Raw(lit.clone())),
+            _ => Ok(DefaultArgs::Expr(expr.clone())),
+        }
+    }
+}
+
+/// Generates the `Option<fn() -> Result<Value, Error>>` producing a default value.
+fn default_value(default: &DefaultArgs, ty: &Type) -> proc_macro2::TokenStream {
+    match default {
+        DefaultArgs::Raw(default) if default.value().is_empty() => quote! { None },
+
+        DefaultArgs::Raw(default) => quote! { Some(|| Ok(config::__internal::Value::from(#default))) },
+
+        DefaultArgs::Expr(default) => quote! {
+            Some(|| {
+                let value: #ty = #default;
+                config::__internal::to_value(&value)
+            })
+        },
+
+        DefaultArgs::Trait => quote! {
+            Some(|| config::__internal::to_value(&<#ty as Default>::default()))
+        },
+    }
+}
+
+/// Per-profile default values, e.g. `profile(production = "otlp", development = "console")`.
+struct ProfileArgs(Vec<(String, DefaultArgs)>);
+
+impl FromMeta for ProfileArgs {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+
+        let profiles = items
+            .iter()
+            .filter_map(|item| match item {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, value, .. }))
+                    if path.get_ident().is_some() =>
+                {
+                    let name = path.get_ident().expect("ident").unraw().to_string();
+                    errors.handle(DefaultArgs::from_expr(value).map(|v| (name, v)))
+                }
+                item => {
+                    errors.push(darling::Error::custom("expected `profile = value`").with_span(item));
+                    None
+                }
+            })
+            .collect();
+
+        errors.finish_with(ProfileArgs(profiles))
+    }
+}
+
+#[derive(Default, FromMeta)]
+struct RangeArgs {
+    min: Option<Bound>,
+    max: Option<Bound>,
+}
+
+struct Bound(Expr);
+
+impl FromMeta for Bound {
+    fn from_expr(expr: &Expr) -> darling::Result<Self> {
+        Ok(Bound(expr.clone()))
+    }
+}
+
+struct OneOfArgs(Vec<Lit>);
+
+impl FromMeta for OneOfArgs {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Lit(lit) => Ok(lit.clone()),
+                NestedMeta::Meta(meta) => {
+                    Err(darling::Error::custom("expected a literal value").with_span(meta))
+                }
+            })
+            .collect::<darling::Result<_>>()
+            .map(OneOfArgs)
+    }
 }
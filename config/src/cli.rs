@@ -0,0 +1,148 @@
+use std::{ffi::OsString, path::Path};
+
+use clap::{parser::ValueSource, Arg, ArgAction, ArgMatches, Command};
+use config_driver::{Map, Value, ValueKind};
+
+use crate::{
+    describe::format_value,
+    prelude::ConfigTrait,
+    schema::{leaves, FieldKind, Leaf, ScalarKind},
+    Error, Loader,
+};
+
+impl<T: ConfigTrait> Loader<T> {
+    /// Returns one long flag per field (e.g. `--section-field` for a `field` within a nested
+    /// `section`), documented from the field doc comment and default value.
+    pub fn args(&self) -> Vec<Arg> {
+        let fields = T::fields();
+        let profile = self.profile();
+
+        leaves(&fields, &self.prefix, &self.separator)
+            .iter()
+            .map(|leaf| {
+                let field = leaf.field;
+                let (help, long_help) = help(field.doc);
+
+                let arg = Arg::new(leaf.key.clone())
+                    .long(flag(leaf))
+                    .value_name(leaf.env.clone())
+                    .help(help)
+                    .long_help(long_help);
+
+                let arg = match field.kind {
+                    FieldKind::List(_) => arg.action(ArgAction::Append),
+                    FieldKind::Map(_) => {
+                        arg.action(ArgAction::Append).value_parser(|value: &str| {
+                            match value.split_once('=') {
+                                Some(_) => Ok(value.to_string()),
+                                None => Err(format!("expected key=value, got {:?}", value)),
+                            }
+                        })
+                    }
+                    FieldKind::Scalar(ScalarKind::Bool) => arg.num_args(0..=1).default_missing_value("true"),
+                    FieldKind::Scalar(_) => arg,
+                };
+
+                let default = field
+                    .default_for(profile.as_deref())
+                    .and_then(|default| default().ok())
+                    .and_then(|value| format_value(&value))
+                    .filter(|v| !v.is_empty());
+
+                match default {
+                    Some(default) if !field.secret => arg.default_value(default),
+                    _ => arg,
+                }
+            })
+            .collect()
+    }
+
+    /// Merges the values explicitly set in the given command-line matches, taking precedence over
+    /// every other source.
+    pub fn with_matches(mut self, matches: &ArgMatches) -> Self {
+        let fields = T::fields();
+
+        for leaf in leaves(&fields, &self.prefix, &self.separator) {
+            if matches.value_source(&leaf.key) != Some(ValueSource::CommandLine) {
+                continue;
+            }
+
+            let Some(values) = matches.get_many::<String>(&leaf.key) else {
+                continue;
+            };
+
+            let origin = format!("--{}", flag(&leaf));
+            let string = |value: &str| Value::new(Some(&origin), ValueKind::String(value.to_string()));
+            let values: Vec<&String> = values.collect();
+
+            let value = match (leaf.field.kind, leaf.field.list_separator) {
+                (FieldKind::Map(_), _) => ValueKind::Table(
+                    values
+                        .iter()
+                        .filter_map(|v| v.split_once('='))
+                        .map(|(key, value)| (key.trim().to_string(), string(value.trim())))
+                        .collect::<Map<_, _>>(),
+                ),
+                (FieldKind::List(_), Some(separator)) => ValueKind::Array(
+                    values
+                        .iter()
+                        .flat_map(|v| v.split(separator))
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(string)
+                        .collect(),
+                ),
+                (FieldKind::List(_), None) => ValueKind::Array(values.iter().map(|v| string(v)).collect()),
+                (FieldKind::Scalar(_), _) => match values.last() {
+                    Some(value) => ValueKind::String(value.to_string()),
+                    None => continue,
+                },
+            };
+
+            self.overrides
+                .push((leaf.key.clone(), Value::new(Some(&origin), value)));
+        }
+
+        self
+    }
+
+    /// Parses the given command-line arguments (the first one being the binary name).
+    pub fn with_args<I, A>(self, args: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<OsString>,
+    {
+        let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+
+        let name = args
+            .first()
+            .and_then(|v| Path::new(v).file_name())
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let matches = self.command(&name).try_get_matches_from(args)?;
+        Ok(self.with_matches(&matches))
+    }
+
+    /// Returns a command accepting every configuration field as a flag.
+    pub fn command(&self, name: &str) -> Command {
+        Command::new(name.to_string()).args(self.args())
+    }
+}
+
+fn flag(leaf: &Leaf) -> String {
+    leaf.key.replace(['.', '_'], "-")
+}
+
+/// Splits a doc comment into its first paragraph and the full text.
+fn help(doc: &str) -> (String, String) {
+    let summary = doc
+        .split("\n\n")
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (summary, doc.to_string())
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+
+use config_driver::{Value, ValueKind};
+
+use crate::{
+    dump::{self, Entry, Format},
+    schema::{leaves, Field, FieldKind},
+    secret::REDACTED,
+    Error,
+};
+
+/// Reference documentation of a configuration, one entry per leaf field.
+#[derive(Clone, Debug)]
+pub struct Description {
+    pub fields: Vec<FieldDescription>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FieldDescription {
+    pub key: String,
+    pub env: String,
+    pub aliases: Vec<&'static str>,
+    pub ty: &'static str,
+    pub kind: FieldKind,
+    pub default: Option<String>,
+    /// Per-profile defaults, `None` when the profile unsets the default.
+    pub profiles: Vec<(&'static str, Option<String>)>,
+    pub doc: &'static str,
+    pub secret: bool,
+    pub(crate) raw_default: Option<Value>,
+}
+
+impl Description {
+    pub(crate) fn new(prefix: &str, separator: &str, fields: &[Field]) -> Result<Self, Error> {
+        let fields = leaves(fields, prefix, separator)
+            .into_iter()
+            .map(|leaf| {
+                let field = leaf.field;
+
+                let redact = |value: Option<String>| match field.secret {
+                    true => value.map(|_| REDACTED.to_string()),
+                    false => value,
+                };
+
+                let raw_default = match field.default {
+                    Some(default) => Some(default()?),
+                    None => None,
+                };
+                let default = raw_default.as_ref().and_then(format_value);
+
+                let profiles = field
+                    .profiles
+                    .iter()
+                    .map(|(name, default)| {
+                        let default = match default {
+                            Some(default) => format_value(&default()?),
+                            None => None,
+                        };
+
+                        Ok((*name, redact(default)))
+                    })
+                    .collect::<Result<_, Error>>()?;
+
+                Ok(FieldDescription {
+                    key: leaf.key,
+                    env: leaf.env,
+                    aliases: field.aliases.clone(),
+                    ty: field.ty,
+                    kind: field.kind,
+                    default: redact(default),
+                    profiles,
+                    doc: field.doc,
+                    secret: field.secret,
+                    raw_default: match raw_default {
+                        Some(_) if field.secret => Some(Value::from(REDACTED)),
+                        raw_default => raw_default,
+                    },
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Description { fields })
+    }
+
+    /// Renders the default values, e.g. to generate a starter configuration file.
+    pub fn dump(&self, format: Format) -> Result<String, Error> {
+        if format == Format::Dotenv {
+            return Ok(self.to_dotenv());
+        }
+
+        dump::render(
+            self.fields.iter().map(|field| Entry {
+                key: &field.key,
+                env: &field.env,
+                kind: field.kind,
+                value: field.raw_default.as_ref(),
+            }),
+            format,
+        )
+    }
+
+    /// Renders the description as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("| Variable | Type | Default | Description |\n|---|---|---|---|\n");
+
+        for field in &self.fields {
+            let _ = writeln!(
+                out,
+                "| `{}` | `{}` | {} | {} |",
+                field.env,
+                field.ty,
+                format_defaults(field),
+                escape_markdown(&field.doc.split_whitespace().collect::<Vec<_>>().join(" ")),
+            );
+        }
+
+        out
+    }
+
+    /// Renders the description as a `.env.example` file, variables without default being commented.
+    pub fn to_dotenv(&self) -> String {
+        let mut out = String::new();
+
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+
+            for line in field.doc.lines() {
+                out.push_str(format!("# {}", line).trim_end());
+                out.push('\n');
+            }
+
+            let _ = writeln!(out, "# Type: {}", field.ty);
+
+            if !field.aliases.is_empty() {
+                let _ = writeln!(out, "# Deprecated aliases: {}", field.aliases.join(", "));
+            }
+
+            if field.secret {
+                let _ = writeln!(
+                    out,
+                    "# Secret, can also be read from the file set in {}_FILE",
+                    field.env
+                );
+            }
+
+            for (name, default) in &field.profiles {
+                let _ = writeln!(
+                    out,
+                    "# Default for the {} profile: {}",
+                    name,
+                    default.as_deref().unwrap_or("<unset>")
+                );
+            }
+
+            match &field.default {
+                Some(default) if !field.secret => {
+                    let _ = writeln!(out, "{}={}", field.env, default);
+                }
+                _ => {
+                    let _ = writeln!(out, "# {}=", field.env);
+                }
+            }
+        }
+
+        out
+    }
+}
+
+/// Formats a value the same way it would be written in an environment variable.
+pub(crate) fn format_value(value: &Value) -> Option<String> {
+    match &value.kind {
+        ValueKind::Nil => None,
+        ValueKind::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(format_value)
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        ValueKind::Table(values) => Some(
+            values
+                .iter()
+                .filter_map(|(key, value)| Some(format!("{}={}", key, format_value(value)?)))
+                .collect::<Vec<_>>()
+                .join(","),
+        ),
+        kind => Some(kind.to_string()),
+    }
+}
+
+fn format_defaults(field: &FieldDescription) -> String {
+    let mut out = field
+        .default
+        .as_ref()
+        .map(|v| format!("`{}`", v))
+        .unwrap_or_default();
+
+    for (name, default) in &field.profiles {
+        if !out.is_empty() {
+            out.push_str("<br>");
+        }
+
+        let _ = match default {
+            Some(default) => write!(out, "{}: `{}`", name, default),
+            None => write!(out, "{}: unset", name),
+        };
+    }
+
+    out
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|")
+}
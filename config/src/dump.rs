@@ -0,0 +1,144 @@
+use std::{fmt::Write, str::FromStr};
+
+use config_driver::{ConfigError, Value, ValueKind};
+use serde_json::{Map, Number};
+
+use crate::{
+    describe::format_value,
+    schema::{FieldKind, ScalarKind},
+    Error,
+};
+
+/// Output format of a configuration dump.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Toml,
+    Yaml,
+    Json,
+    Dotenv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "toml" => Ok(Format::Toml),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "json" => Ok(Format::Json),
+            "env" | "dotenv" => Ok(Format::Dotenv),
+            _ => Err(format!("unsupported format: {}", s)),
+        }
+    }
+}
+
+/// Field value to dump, secrets being already redacted.
+pub(crate) struct Entry<'a> {
+    pub(crate) key: &'a str,
+    pub(crate) env: &'a str,
+    pub(crate) kind: FieldKind,
+    pub(crate) value: Option<&'a Value>,
+}
+
+/// Renders field values, file formats nesting them by key so that the output can be loaded back.
+pub(crate) fn render<'a>(
+    entries: impl IntoIterator<Item = Entry<'a>>,
+    format: Format,
+) -> Result<String, Error> {
+    let entries: Vec<Entry> = entries.into_iter().collect();
+
+    if format == Format::Dotenv {
+        let mut out = String::new();
+
+        for entry in entries {
+            let _ = match entry.value.and_then(format_value) {
+                Some(value) => writeln!(out, "{}={}", entry.env, value),
+                None => writeln!(out, "# {}=", entry.env),
+            };
+        }
+
+        return Ok(out);
+    }
+
+    let mut root = Map::new();
+
+    for entry in entries {
+        let Some(value) = entry.value.and_then(|v| to_json(v, scalar_kind(entry.kind))) else {
+            continue;
+        };
+
+        let mut segments: Vec<&str> = entry.key.split('.').collect();
+        let name = segments.pop().unwrap_or_default();
+
+        let table = segments.into_iter().fold(&mut root, |table, segment| {
+            match table
+                .entry(segment)
+                .or_insert_with(|| serde_json::Value::Object(Map::new()))
+            {
+                serde_json::Value::Object(table) => table,
+                _ => unreachable!("field keys are either tables or values"),
+            }
+        });
+
+        table.insert(name.to_string(), value);
+    }
+
+    let root = serde_json::Value::Object(root);
+
+    match format {
+        Format::Toml => toml::to_string_pretty(&root).map_err(foreign),
+        Format::Yaml => serde_yaml::to_string(&root).map_err(foreign),
+        Format::Json => serde_json::to_string_pretty(&root)
+            .map(|v| v + "\n")
+            .map_err(foreign),
+        Format::Dotenv => unreachable!(),
+    }
+}
+
+/// Returns the kind of the scalar values of a field, so that values read from environment variables
+/// (always strings) are dumped with their actual type.
+fn scalar_kind(kind: FieldKind) -> ScalarKind {
+    match kind {
+        FieldKind::Scalar(kind) | FieldKind::List(kind) | FieldKind::Map(kind) => kind,
+    }
+}
+
+fn to_json(value: &Value, kind: ScalarKind) -> Option<serde_json::Value> {
+    Some(match &value.kind {
+        ValueKind::Nil => return None,
+        ValueKind::Boolean(value) => serde_json::Value::Bool(*value),
+        ValueKind::I64(value) => serde_json::Value::from(*value),
+        ValueKind::U64(value) => serde_json::Value::from(*value),
+        ValueKind::I128(value) => serde_json::Value::from(*value as f64),
+        ValueKind::U128(value) => serde_json::Value::from(*value as f64),
+        ValueKind::Float(value) => serde_json::Value::from(*value),
+        ValueKind::String(value) => coerce(value, kind),
+        ValueKind::Array(values) => values.iter().filter_map(|v| to_json(v, kind)).collect(),
+        ValueKind::Table(values) => serde_json::Value::Object(
+            values
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), to_json(value, kind)?)))
+                .collect(),
+        ),
+    })
+}
+
+fn coerce(value: &str, kind: ScalarKind) -> serde_json::Value {
+    let typed = match kind {
+        ScalarKind::Bool => value.parse().ok().map(serde_json::Value::Bool),
+        ScalarKind::Signed => value.parse::<i64>().ok().map(Into::into),
+        ScalarKind::Unsigned => value.parse::<u64>().ok().map(Into::into),
+        ScalarKind::Float => value
+            .parse()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(serde_json::Value::Number),
+        ScalarKind::Other => None,
+    };
+
+    typed.unwrap_or_else(|| serde_json::Value::String(value.to_string()))
+}
+
+fn foreign<E: std::error::Error + Send + Sync + 'static>(err: E) -> Error {
+    Error::Load(ConfigError::Foreign(Box::new(err)))
+}
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, env, fs, iter};
+
+use config_driver::{ConfigError, Map, Source, Value, ValueKind};
+
+use crate::{
+    schema::{leaves, Field},
+    FieldError,
+};
+
+const FILE_SUFFIX: &str = "_FILE";
+const DEFAULT_LIST_SEPARATOR: &str = ",";
+
+/// Environment source only looking up variables matching known configuration fields, which allows
+/// mapping `PREFIX_SECTION_SOME_FIELD` to `section.some_field` even when the separator is also used
+/// within field names.
+///
+/// Secret fields can also be read from the file pointed by the `_FILE`-suffixed variable (e.g.
+/// `PREFIX_PASSWORD_FILE`), the plain variable taking precedence when both are set. Field aliases
+/// are only looked up when the main variable is missing, their use being logged as a warning and
+/// reported as deprecated in the resolved field origins.
+///
+/// List fields are split on their separator (`a,b,c`), and map fields are parsed as `key=value`
+/// entries (`a=1,b=2`).
+///
+/// Variables are read from the process environment, or from an in-memory map when one is given.
+#[derive(Clone, Debug)]
+pub(crate) struct EnvironmentSource {
+    vars: Vec<Var>,
+    env: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone, Debug)]
+struct Var {
+    key: String,
+    names: Vec<String>,
+    list_separator: Option<&'static str>,
+    map: bool,
+    secret: bool,
+}
+
+impl EnvironmentSource {
+    pub(crate) fn new(
+        prefix: &str,
+        separator: &str,
+        fields: &[Field],
+        env: Option<&HashMap<String, String>>,
+    ) -> Self {
+        let vars = leaves(fields, prefix, separator)
+            .into_iter()
+            .map(|leaf| Var {
+                names: iter::once(leaf.env)
+                    .chain(leaf.field.aliases.iter().map(ToString::to_string))
+                    .collect(),
+                key: leaf.key,
+                list_separator: leaf.field.list_separator,
+                map: leaf.field.map,
+                secret: leaf.field.secret,
+            })
+            .collect();
+
+        EnvironmentSource {
+            vars,
+            env: env.cloned(),
+        }
+    }
+
+    /// Looks up the field variables in order, returning the actual variable name along with its
+    /// value.
+    fn lookup(&self, var: &Var) -> Result<Option<(String, String)>, ConfigError> {
+        for name in &var.names {
+            if let Some(value) = var_value(self.env.as_ref(), name) {
+                return Ok(Some((name.clone(), value)));
+            }
+
+            if var.secret {
+                let name = format!("{}{}", name, FILE_SUFFIX);
+
+                if let Some(path) = var_value(self.env.as_ref(), &name) {
+                    return Ok(Some((name, read_secret(&path)?)));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Reads a variable from the given map, or from the process environment when there is none.
+pub(crate) fn var_value(env: Option<&HashMap<String, String>>, name: &str) -> Option<String> {
+    match env {
+        Some(env) => env.get(name).cloned(),
+        None => env::var(name).ok(),
+    }
+}
+
+impl Source for EnvironmentSource {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        let mut map = Map::new();
+
+        for var in &self.vars {
+            let Some((origin, value)) = self.lookup(var)? else {
+                continue;
+            };
+
+            // Malformed values are kept as is, and reported along with the other field errors.
+            let value = parse(var, &origin, &value).unwrap_or(ValueKind::String(value));
+
+            map.insert(var.key.clone(), Value::new(Some(&origin), value));
+        }
+
+        Ok(map)
+    }
+}
+
+impl EnvironmentSource {
+    /// Returns the list and map variables whose value cannot be parsed.
+    pub(crate) fn errors(&self) -> Result<Vec<FieldError>, ConfigError> {
+        let mut errors = Vec::new();
+
+        for var in &self.vars {
+            let Some((origin, value)) = self.lookup(var)? else {
+                continue;
+            };
+
+            if let Err(message) = parse(var, &origin, &value) {
+                errors.push(FieldError {
+                    key: var.key.clone(),
+                    env: origin,
+                    message,
+                });
+            }
+        }
+
+        Ok(errors)
+    }
+}
+
+fn parse(var: &Var, origin: &String, value: &str) -> Result<ValueKind, String> {
+    let separator = match (var.list_separator, var.map) {
+        (Some(separator), _) => separator,
+        (None, true) => DEFAULT_LIST_SEPARATOR,
+        (None, false) => return Ok(ValueKind::String(value.to_string())),
+    };
+
+    let items = value.split(separator).map(str::trim).filter(|v| !v.is_empty());
+
+    if var.map {
+        items
+            .map(|item| match item.split_once('=') {
+                Some((key, value)) => Ok((
+                    key.trim().to_string(),
+                    Value::new(Some(origin), ValueKind::String(value.trim().to_string())),
+                )),
+                None => Err(format!("invalid map entry: expected key=value, got {:?}", item)),
+            })
+            .collect::<Result<Map<_, _>, _>>()
+            .map(ValueKind::Table)
+    } else {
+        Ok(ValueKind::Array(
+            items
+                .map(|item| Value::new(Some(origin), ValueKind::String(item.to_string())))
+                .collect(),
+        ))
+    }
+}
+
+fn read_secret(path: &str) -> Result<String, ConfigError> {
+    fs::read_to_string(path)
+        .map(|v| v.trim_end_matches(['\r', '\n']).to_string())
+        .map_err(|v| ConfigError::Message(format!("failed to read secret file {}: {}", path, v)))
+}
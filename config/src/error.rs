@@ -0,0 +1,48 @@
+use std::fmt;
+
+use errors::prelude::*;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Load(#[from] config_driver::ConfigError),
+
+    #[cfg(feature = "cli")]
+    #[error("{0}")]
+    Cli(#[from] clap::Error),
+
+    #[error("{name}: {error:#}")]
+    Source {
+        name: String,
+        #[source]
+        error: AnyError,
+    },
+
+    #[error("invalid configuration: {}", join(.0))]
+    Validation(Vec<FieldError>),
+}
+
+/// Validation failure for a single configuration field.
+#[derive(Debug)]
+pub struct FieldError {
+    pub key: String,
+    pub env: String,
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.env, self.key, self.message)
+    }
+}
+
+fn join(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -1,13 +1,110 @@
+pub use config_driver::Value;
+pub use describe::{Description, FieldDescription};
+pub use dump::Format;
+pub use error::{Error, FieldError};
+pub use loader::Loader;
+pub use provenance::{Origin, Resolution, ResolvedField};
+#[cfg(feature = "reload")]
+pub use reload::{ConfigHandle, Reload};
+pub use schema::{DefaultFn, Field, FieldKind, ScalarKind};
+pub use secret::Secret;
+pub use source::{AsyncConfigSource, ConfigSource, DirectorySource, MapSource};
+
+#[cfg(feature = "cli")]
+mod cli;
+mod describe;
+mod dump;
+mod env;
+mod error;
+mod loader;
+mod provenance;
+#[cfg(feature = "reload")]
+mod reload;
+mod schema;
+mod secret;
+mod source;
+
+pub mod types;
+pub mod validate;
+
 pub mod prelude {
-    pub use config_driver::ConfigError;
+    use std::collections::HashMap;
+
+    use config_driver::Value;
+    use serde::de::DeserializeOwned;
+
+    pub use crate::Error as ConfigError;
 
     pub use config_derive::Config;
 
-    pub trait ConfigTrait: Sized {
-        fn from_env(prefix: &str) -> Result<Self, ConfigError>;
+    use crate::{validate::Violations, Description, Field, Format, Loader, Resolution};
+
+    pub trait ConfigTrait: Sized + DeserializeOwned {
+        fn fields() -> Vec<Field>;
+
+        fn validate(&self, _violations: &mut Violations) {}
+
+        fn loader(prefix: &str) -> Loader<Self> {
+            Loader::new(prefix)
+        }
+
+        /// Loads the configuration from its sources, deprecated variables being logged as warnings
+        /// through `tracing` (`resolve` reporting them as `Origin::Env { deprecated: true, .. }`).
+        fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+            Self::loader(prefix).load()
+        }
+
+        /// Loads the configuration from the given variables instead of the process environment.
+        fn from_map(prefix: &str, vars: HashMap<String, String>) -> Result<Self, ConfigError> {
+            Self::loader(prefix).with_env(vars).load()
+        }
+
+        /// Loads the configuration from its defaults and the given overrides only, ignoring the
+        /// process environment (mostly useful in tests).
+        fn from_overrides<I, K, V>(overrides: I) -> Result<Self, ConfigError>
+        where
+            I: IntoIterator<Item = (K, V)>,
+            K: AsRef<str>,
+            V: Into<Value>,
+        {
+            overrides
+                .into_iter()
+                .fold(
+                    Self::loader("").with_env(HashMap::<String, String>::new()),
+                    |loader, (key, value)| loader.with_override(key.as_ref(), value),
+                )
+                .load()
+        }
+
+        /// Loads the configuration, merging the process command-line arguments over every other
+        /// source, and exits with a usage message when they are invalid or `--help` is passed.
+        #[cfg(feature = "cli")]
+        fn from_args(prefix: &str) -> Result<Self, ConfigError> {
+            match Self::loader(prefix).with_args(std::env::args_os()) {
+                Ok(loader) => loader.load(),
+                Err(ConfigError::Cli(err)) => err.exit(),
+                Err(err) => Err(err),
+            }
+        }
+
+        fn resolve(prefix: &str) -> Result<(Self, Resolution), ConfigError> {
+            Self::loader(prefix).resolve()
+        }
+
+        /// Renders the effective configuration, secrets being redacted.
+        fn dump(prefix: &str, format: Format) -> Result<String, ConfigError> {
+            Self::resolve(prefix)?.1.dump(format)
+        }
+
+        fn describe(prefix: &str) -> Result<Description, ConfigError> {
+            Self::loader(prefix).describe()
+        }
     }
 }
 
 pub mod __internal {
-    pub use config_driver::{Config, Environment};
+    pub use config_driver::Value;
+    pub use serde;
+
+    pub use crate::schema::to_value;
 }
@@ -0,0 +1,386 @@
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use config_driver::{Config, File, Value, ValueKind};
+use errors::prelude::anyhow;
+
+use crate::{
+    describe::Description,
+    env::{var_value, EnvironmentSource},
+    prelude::ConfigTrait,
+    provenance::{Origin, Resolution},
+    schema::{leaves, Leaf},
+    source::{AsyncConfigSource, Collected, ConfigSource},
+    validate::Violations,
+    Error, FieldError,
+};
+
+/// Layered configuration loader.
+///
+/// Sources are merged in the following order, each one taking precedence over the previous ones:
+///
+/// 1. defaults declared with `#[config(default = ...)]`, or `#[config(profile(name = ...))]` for the
+///    active profile
+/// 2. files, in the order they were added (each followed by its profile-specific variant, if any)
+/// 3. pluggable sources, in the order they were added
+/// 4. environment variables matching the loader prefix (e.g. `PREFIX_SECTION_FIELD` for a `field`
+///    within a nested `section`)
+/// 5. explicit overrides, including command-line arguments
+///
+/// Every field value is first checked against its type, then the deserialized configuration is
+/// validated, every failing field being reported at once.
+pub struct Loader<T> {
+    pub(crate) prefix: String,
+    pub(crate) separator: String,
+    defaults: Vec<(String, Value)>,
+    files: Vec<(PathBuf, bool)>,
+    profile: Option<String>,
+    profile_var: String,
+    env: Option<HashMap<String, String>>,
+    sources: Vec<Plugged>,
+    pub(crate) overrides: Vec<(String, Value)>,
+    marker: PhantomData<T>,
+}
+
+impl<T> Loader<T> {
+    pub fn new(prefix: &str) -> Self {
+        Loader {
+            prefix: prefix.to_string(),
+            separator: "_".to_string(),
+            defaults: Vec::new(),
+            files: Vec::new(),
+            profile: None,
+            profile_var: "ENV".to_string(),
+            env: None,
+            sources: Vec::new(),
+            overrides: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the separator used between the prefix and nested field names in environment variable
+    /// names (defaults to `_`).
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    pub fn with_default<V: Into<Value>>(mut self, key: &str, value: V) -> Self {
+        self.defaults.push((key.to_string(), value.into()));
+        self
+    }
+
+    /// Adds a file source, its format being inferred from the file extension (TOML, YAML, JSON...).
+    pub fn with_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.files.push((path.as_ref().to_path_buf(), true));
+        self
+    }
+
+    /// Adds a file source that is silently skipped when missing.
+    pub fn with_optional_file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.files.push((path.as_ref().to_path_buf(), false));
+        self
+    }
+
+    /// Sets the profile used to select per-profile defaults and look up per-environment files (e.g.
+    /// `config.production.toml` when loading `config.toml` with the `production` profile).
+    ///
+    /// The profile is otherwise read from the profile environment variable.
+    pub fn with_profile(mut self, profile: &str) -> Self {
+        self.profile = Some(profile.to_string());
+        self
+    }
+
+    /// Sets the environment variable the profile is read from (defaults to `ENV`).
+    pub fn with_profile_var(mut self, name: &str) -> Self {
+        self.profile_var = name.to_string();
+        self
+    }
+
+    /// Returns the active profile, if any.
+    pub fn profile(&self) -> Option<String> {
+        match &self.profile {
+            Some(profile) => Some(profile.clone()),
+            None => var_value(self.env.as_ref(), &self.profile_var).filter(|v| !v.is_empty()),
+        }
+    }
+
+    /// Reads environment variables from the given map instead of the process environment, which
+    /// allows loading configurations in tests without mutating global state.
+    pub fn with_env<I, K, V>(mut self, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = Some(vars.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+        self
+    }
+
+    pub fn with_source<S: ConfigSource + 'static>(mut self, source: S) -> Self {
+        self.sources.push(Plugged::Sync(Box::new(source)));
+        self
+    }
+
+    /// Adds an asynchronous source, the configuration then having to be loaded with `load_async`.
+    pub fn with_async_source<S: AsyncConfigSource + 'static>(mut self, source: S) -> Self {
+        self.sources.push(Plugged::Async(Box::new(source)));
+        self
+    }
+
+    pub fn with_override<V: Into<Value>>(mut self, key: &str, value: V) -> Self {
+        self.overrides.push((key.to_string(), value.into()));
+        self
+    }
+}
+
+impl<T: ConfigTrait> Loader<T> {
+    /// Describes the fields accepted by the configuration, along with their environment variable names.
+    pub fn describe(&self) -> Result<Description, Error> {
+        Description::new(&self.prefix, &self.separator, &T::fields())
+    }
+
+    pub fn load(&self) -> Result<T, Error> {
+        self.resolve().map(|(value, _)| value)
+    }
+
+    /// Loads the configuration along with the origin of every field value.
+    pub fn resolve(&self) -> Result<(T, Resolution), Error> {
+        let sources = self
+            .sources
+            .iter()
+            .map(|source| match source {
+                Plugged::Sync(source) => collect(source.name(), source.collect()),
+                Plugged::Async(source) => Err(Error::Source {
+                    name: source.name(),
+                    error: anyhow!("asynchronous sources require `load_async`"),
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+
+        self.resolve_with(sources)
+    }
+
+    pub async fn load_async(&self) -> Result<T, Error> {
+        self.resolve_async().await.map(|(value, _)| value)
+    }
+
+    /// Loads the configuration along with the origin of every field value, collecting asynchronous
+    /// sources along the way.
+    pub async fn resolve_async(&self) -> Result<(T, Resolution), Error> {
+        let mut sources = Vec::new();
+
+        for source in &self.sources {
+            sources.push(match source {
+                Plugged::Sync(source) => collect(source.name(), source.collect())?,
+                Plugged::Async(source) => collect(source.name(), source.collect().await)?,
+            });
+        }
+
+        self.resolve_with(sources)
+    }
+
+    fn resolve_with(&self, sources: Vec<Collected>) -> Result<(T, Resolution), Error> {
+        let fields = T::fields();
+        let profile = self.profile();
+
+        let mut builder = Config::builder();
+
+        let leaves = leaves(&fields, &self.prefix, &self.separator);
+
+        for leaf in &leaves {
+            if let Some(default) = leaf.field.default_for(profile.as_deref()) {
+                builder = builder.set_default(&leaf.key, default()?)?;
+            }
+        }
+
+        for (key, value) in &self.defaults {
+            builder = builder.set_default(key, value.clone())?;
+        }
+
+        for (path, required) in self.file_paths() {
+            builder = builder.add_source(File::from(path).required(required));
+        }
+
+        let names: Vec<String> = sources.iter().map(|v| v.name.clone()).collect();
+
+        for source in sources {
+            builder = builder.add_source(source);
+        }
+
+        let env = EnvironmentSource::new(&self.prefix, &self.separator, &fields, self.env.as_ref());
+        let mut errors = env.errors()?;
+
+        builder = builder.add_source(env);
+
+        for (key, value) in &self.overrides {
+            builder = builder.set_override(key, value.clone())?;
+        }
+
+        let config = builder.build()?;
+
+        let mismatches: Vec<FieldError> = leaves
+            .iter()
+            .filter(|leaf| !errors.iter().any(|error| error.key == leaf.key))
+            .filter_map(|leaf| {
+                let check = leaf.field.check?;
+                let value = config.get::<Value>(&leaf.key).ok()?;
+
+                check(value).err().map(|err| FieldError {
+                    key: leaf.key.clone(),
+                    env: leaf.env.clone(),
+                    message: err.to_string(),
+                })
+            })
+            .collect();
+        errors.extend(mismatches);
+
+        if !errors.is_empty() {
+            // Falls back to the defaults of the invalid fields so that the other ones still get
+            // validated, every error being reported at once.
+            let mut cache = config.cache.clone();
+
+            for error in &errors {
+                let default = leaves
+                    .iter()
+                    .find(|v| v.key == error.key)
+                    .and_then(|v| v.field.default_for(profile.as_deref()))
+                    .and_then(|default| default().ok());
+
+                replace(&mut cache, &error.key, default.unwrap_or_default());
+            }
+
+            if let Ok(value) = cache.try_deserialize::<T>() {
+                let violations: Vec<FieldError> = violations(&value, &leaves)
+                    .into_iter()
+                    .filter(|v| !errors.iter().any(|error| error.key == v.key))
+                    .collect();
+
+                errors.extend(violations);
+            }
+
+            return Err(Error::Validation(errors));
+        }
+
+        let resolution = Resolution::new(
+            &config,
+            &leaves,
+            profile.as_deref(),
+            &self.defaults,
+            &names,
+            &self.overrides,
+        );
+
+        for field in &resolution.fields {
+            if let Origin::Env {
+                name,
+                deprecated: true,
+            } = &field.origin
+            {
+                tracing::warn!(
+                    config.key = field.key,
+                    "environment variable {} is deprecated, use {} instead",
+                    name,
+                    field.env
+                );
+            }
+        }
+
+        let value: T = config.try_deserialize()?;
+        let errors = violations(&value, &leaves);
+
+        if errors.is_empty() {
+            Ok((value, resolution))
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+}
+
+/// Replaces the value at the given key, if its parent table exists.
+fn replace(root: &mut Value, key: &str, value: Value) {
+    let mut segments: Vec<&str> = key.split('.').collect();
+    let name = segments.pop().unwrap_or_default();
+
+    let parent = segments
+        .into_iter()
+        .try_fold(root, |value, segment| match &mut value.kind {
+            ValueKind::Table(table) => table.get_mut(segment),
+            _ => None,
+        });
+
+    if let Some(Value {
+        kind: ValueKind::Table(table),
+        ..
+    }) = parent
+    {
+        table.insert(name.to_string(), value);
+    }
+}
+
+fn violations<T: ConfigTrait>(value: &T, leaves: &[Leaf]) -> Vec<FieldError> {
+    let mut violations = Violations::default();
+    value.validate(&mut violations);
+
+    violations
+        .into_items()
+        .into_iter()
+        .map(|(path, message)| {
+            let key = path.join(".");
+            let env = leaves.iter().find(|v| v.key == key).map(|v| v.env.clone());
+
+            FieldError {
+                env: env.unwrap_or_default(),
+                key,
+                message,
+            }
+        })
+        .collect()
+}
+
+impl<T> Loader<T> {
+    /// Returns the file sources in loading order, along with their requirement flag.
+    pub(crate) fn file_paths(&self) -> Vec<(PathBuf, bool)> {
+        let profile = self.profile();
+        let mut paths = Vec::new();
+
+        for (path, required) in &self.files {
+            paths.push((path.clone(), *required));
+
+            if let Some(path) = profile.as_deref().and_then(|v| profile_path(path, v)) {
+                paths.push((path, false));
+            }
+        }
+
+        paths
+    }
+}
+
+enum Plugged {
+    Sync(Box<dyn ConfigSource>),
+    Async(Box<dyn AsyncConfigSource>),
+}
+
+fn collect(
+    name: String,
+    values: errors::prelude::Result<HashMap<String, Value>>,
+) -> Result<Collected, Error> {
+    match values {
+        Ok(values) => Ok(Collected { name, values }),
+        Err(error) => Err(Error::Source { name, error }),
+    }
+}
+
+fn profile_path(path: &Path, profile: &str) -> Option<PathBuf> {
+    let stem = path.file_stem()?.to_str()?;
+
+    let name = match path.extension().and_then(|v| v.to_str()) {
+        Some(extension) => format!("{}.{}.{}", stem, profile, extension),
+        None => format!("{}.{}", stem, profile),
+    };
+
+    Some(path.with_file_name(name))
+}
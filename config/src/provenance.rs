@@ -0,0 +1,208 @@
+use std::fmt;
+
+use config_driver::{Config, Value, ValueKind};
+
+use crate::{
+    describe::format_value,
+    dump::{self, Entry, Format},
+    schema::{FieldKind, Leaf},
+    secret::REDACTED,
+    Error,
+};
+
+/// Where a resolved configuration value came from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Origin {
+    Default,
+    Profile(String),
+    /// Environment variable, `deprecated` being set when it is one of the field aliases.
+    Env {
+        name: String,
+        deprecated: bool,
+    },
+    File {
+        path: String,
+        key: String,
+    },
+    Source(String),
+    Override,
+    Arg(String),
+    Unset,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Default => f.write_str("default"),
+            Origin::Profile(name) => write!(f, "{} profile default", name),
+            Origin::Env {
+                name,
+                deprecated: false,
+            } => write!(f, "env {}", name),
+            Origin::Env {
+                name,
+                deprecated: true,
+            } => write!(f, "env {} (deprecated)", name),
+            Origin::File { path, key } => write!(f, "file {} ({})", path, key),
+            Origin::Source(name) => write!(f, "source {}", name),
+            Origin::Override => f.write_str("override"),
+            Origin::Arg(flag) => write!(f, "argument {}", flag),
+            Origin::Unset => f.write_str("unset"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResolvedField {
+    pub key: String,
+    pub env: String,
+    pub ty: &'static str,
+    pub kind: FieldKind,
+    /// Value formatted the same way it would be set in an environment variable, secrets being
+    /// redacted.
+    pub value: Option<String>,
+    pub origin: Origin,
+    pub(crate) raw: Option<Value>,
+}
+
+/// Resolved configuration fields along with their origin.
+#[derive(Clone, Debug)]
+pub struct Resolution {
+    pub fields: Vec<ResolvedField>,
+}
+
+impl Resolution {
+    pub(crate) fn new(
+        config: &Config,
+        leaves: &[Leaf],
+        profile: Option<&str>,
+        defaults: &[(String, Value)],
+        sources: &[String],
+        overrides: &[(String, Value)],
+    ) -> Self {
+        let fields = leaves
+            .iter()
+            .map(|leaf| {
+                let value = lookup(&config.cache, &leaf.key);
+
+                let last_override = overrides.iter().rev().find(|(key, _)| *key == leaf.key);
+
+                // Tables merged from several layers are rebuilt without an origin of their own, the
+                // highest-precedence origin of their entries being used instead.
+                let rank = |origin: &str| match origin {
+                    origin if is_env(leaf, origin) => 2,
+                    origin if sources.iter().any(|v| v == origin) => 1,
+                    _ => 0,
+                };
+
+                let origin = match value.map(|v| origin_of(v, &rank)) {
+                    None => Origin::Unset,
+                    _ if last_override.is_some() => match last_override.and_then(|(_, v)| v.origin()) {
+                        Some(flag) => Origin::Arg(flag.to_string()),
+                        None => Origin::Override,
+                    },
+                    Some(None) => match profile {
+                        Some(profile)
+                            if leaf.field.profiles.iter().any(|(name, _)| *name == profile)
+                                && !defaults.iter().any(|(key, _)| *key == leaf.key) =>
+                        {
+                            Origin::Profile(profile.to_string())
+                        }
+                        _ => Origin::Default,
+                    },
+                    Some(Some(origin)) if is_env(leaf, origin) => Origin::Env {
+                        name: origin.to_string(),
+                        deprecated: is_alias(leaf, origin),
+                    },
+                    Some(Some(origin)) if sources.iter().any(|v| v == origin) => {
+                        Origin::Source(origin.to_string())
+                    }
+                    Some(Some(origin)) => Origin::File {
+                        path: origin.to_string(),
+                        key: leaf.key.clone(),
+                    },
+                };
+
+                let raw = match value {
+                    Some(_) if leaf.field.secret => Some(Value::from(REDACTED)),
+                    value => value.cloned(),
+                };
+
+                ResolvedField {
+                    key: leaf.key.clone(),
+                    env: leaf.env.clone(),
+                    ty: leaf.field.ty,
+                    kind: leaf.field.kind,
+                    value: raw.as_ref().and_then(format_value),
+                    origin,
+                    raw,
+                }
+            })
+            .collect();
+
+        Resolution { fields }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&ResolvedField> {
+        self.fields.iter().find(|v| v.key == key)
+    }
+
+    /// Renders the effective configuration, secrets being redacted.
+    pub fn dump(&self, format: Format) -> Result<String, Error> {
+        dump::render(
+            self.fields.iter().map(|field| Entry {
+                key: &field.key,
+                env: &field.env,
+                kind: field.kind,
+                value: field.raw.as_ref(),
+            }),
+            format,
+        )
+    }
+}
+
+/// Formats a startup summary, one line per field.
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for field in &self.fields {
+            writeln!(
+                f,
+                "{} = {} ({})",
+                field.key,
+                field.value.as_deref().unwrap_or("<unset>"),
+                field.origin
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Looks up a value in the configuration cache, preserving its origin (unlike `Config::get`).
+fn lookup<'a>(cache: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(cache, |value, segment| match &value.kind {
+            ValueKind::Table(table) => table.get(segment),
+            _ => None,
+        })
+}
+
+fn origin_of<'a>(value: &'a Value, rank: &dyn Fn(&str) -> usize) -> Option<&'a str> {
+    match (value.origin(), &value.kind) {
+        (None, ValueKind::Table(table)) => table
+            .values()
+            .filter_map(|v| origin_of(v, rank))
+            .max_by_key(|v| rank(v)),
+        (origin, _) => origin,
+    }
+}
+
+fn is_env(leaf: &Leaf, origin: &str) -> bool {
+    let name = origin.strip_suffix("_FILE").unwrap_or(origin);
+    leaf.env == name || is_alias(leaf, origin)
+}
+
+fn is_alias(leaf: &Leaf, origin: &str) -> bool {
+    let name = origin.strip_suffix("_FILE").unwrap_or(origin);
+    leaf.env != name && leaf.field.aliases.contains(&name)
+}
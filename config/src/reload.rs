@@ -0,0 +1,143 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, RwLock, Weak,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{prelude::ConfigTrait, Error, Loader};
+
+/// Outcome of a configuration reload: either the new value or the error that caused it to be
+/// rejected, in which case the current value is left untouched.
+pub type Reload<T> = Result<Arc<T>, Arc<Error>>;
+
+/// Shared handle to a configuration value reloaded in the background.
+pub struct ConfigHandle<T> {
+    shared: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    current: RwLock<Arc<T>>,
+    subscribers: Mutex<Vec<mpsc::Sender<Reload<T>>>>,
+}
+
+impl<T> ConfigHandle<T> {
+    fn new(value: T) -> Self {
+        ConfigHandle {
+            shared: Arc::new(Shared {
+                current: RwLock::new(Arc::new(value)),
+                subscribers: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    pub fn current(&self) -> Arc<T> {
+        self.shared
+            .current
+            .read()
+            .unwrap_or_else(|v| v.into_inner())
+            .clone()
+    }
+
+    /// Returns a receiver notified after each reload attempt.
+    pub fn subscribe(&self) -> mpsc::Receiver<Reload<T>> {
+        let (tx, rx) = mpsc::channel();
+        self.shared
+            .subscribers
+            .lock()
+            .unwrap_or_else(|v| v.into_inner())
+            .push(tx);
+        rx
+    }
+}
+
+impl<T> Clone for ConfigHandle<T> {
+    fn clone(&self) -> Self {
+        ConfigHandle {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    fn publish(&self, reload: Result<T, Error>) {
+        let reload = match reload {
+            Ok(value) => {
+                let value = Arc::new(value);
+                *self.current.write().unwrap_or_else(|v| v.into_inner()) = value.clone();
+                Ok(value)
+            }
+
+            Err(err) => Err(Arc::new(err)),
+        };
+
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|v| v.into_inner())
+            .retain(|tx| tx.send(reload.clone()).is_ok());
+    }
+}
+
+impl<T> Loader<T>
+where
+    T: ConfigTrait + Send + Sync + 'static,
+{
+    /// Loads the configuration and spawns a background thread reloading it whenever one of the file
+    /// sources changes (checked every `interval`) or the process receives `SIGHUP`.
+    ///
+    /// The thread stops once every handle has been dropped, unregistering its `SIGHUP` handler.
+    pub fn watch(self, interval: Duration) -> Result<ConfigHandle<T>, Error> {
+        let handle = ConfigHandle::new(self.load()?);
+
+        let hangup = Arc::new(AtomicBool::new(false));
+        #[cfg(unix)]
+        let signal = signal_hook::flag::register(signal_hook::consts::SIGHUP, hangup.clone())?;
+
+        let shared = Arc::downgrade(&handle.shared);
+
+        thread::spawn(move || {
+            watch(self, shared, hangup, interval);
+
+            #[cfg(unix)]
+            signal_hook::low_level::unregister(signal);
+        });
+
+        Ok(handle)
+    }
+}
+
+fn watch<T: ConfigTrait>(
+    loader: Loader<T>,
+    shared: Weak<Shared<T>>,
+    hangup: Arc<AtomicBool>,
+    interval: Duration,
+) {
+    let paths: Vec<PathBuf> = loader.file_paths().into_iter().map(|(path, _)| path).collect();
+    let mut modified = modified_times(&paths);
+
+    loop {
+        thread::sleep(interval);
+
+        let Some(shared) = shared.upgrade() else {
+            break;
+        };
+
+        let current = modified_times(&paths);
+
+        if hangup.swap(false, Ordering::Relaxed) || current != modified {
+            modified = current;
+            shared.publish(loader.load());
+        }
+    }
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|v| v.modified()).ok())
+        .collect()
+}
@@ -0,0 +1,150 @@
+use config_driver::{ConfigError, Map, Value, ValueKind};
+use serde::Serialize;
+
+use crate::Error;
+
+/// Generates a field default value.
+pub type DefaultFn = fn() -> Result<Value, Error>;
+
+/// Configuration field metadata, as generated by `#[derive(Config)]`.
+#[derive(Clone, Debug, Default)]
+pub struct Field {
+    pub name: &'static str,
+    pub ty: &'static str,
+    pub doc: &'static str,
+    pub default: Option<DefaultFn>,
+    /// Per-profile defaults, taking precedence over `default` when the profile is active.
+    pub profiles: Vec<(&'static str, Option<DefaultFn>)>,
+    /// Checks a value can be deserialized into the field type.
+    pub check: Option<fn(Value) -> Result<(), ConfigError>>,
+    /// Name segment used to build the field environment variable name (defaults to the uppercased
+    /// field name).
+    pub env_key: &'static str,
+    /// Absolute environment variable name, overriding the one built from the prefix.
+    pub env: Option<&'static str>,
+    /// Deprecated environment variable names still accepted for the field.
+    pub aliases: Vec<&'static str>,
+    /// Separator used to split environment variable values into lists (or map entries).
+    pub list_separator: Option<&'static str>,
+    /// Whether environment variable values are parsed as `key=value` map entries (keys being
+    /// lowercased, as for every other source).
+    pub map: bool,
+    pub kind: FieldKind,
+    pub secret: bool,
+    pub nested: Option<Vec<Field>>,
+}
+
+/// Shape of a field type (looking through `Option`), as seen by `#[derive(Config)]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldKind {
+    Scalar(ScalarKind),
+    /// Sequence (e.g. `Vec` or `HashSet`), along with the kind of its items.
+    List(ScalarKind),
+    /// Map (e.g. `HashMap` or `BTreeMap`), along with the kind of its values.
+    Map(ScalarKind),
+}
+
+impl Default for FieldKind {
+    fn default() -> Self {
+        FieldKind::Scalar(ScalarKind::Other)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ScalarKind {
+    Bool,
+    Signed,
+    Unsigned,
+    Float,
+    #[default]
+    Other,
+}
+
+impl Field {
+    /// Returns the default value generator of the field for the given profile.
+    pub fn default_for(&self, profile: Option<&str>) -> Option<DefaultFn> {
+        match self.profiles.iter().find(|(name, _)| Some(*name) == profile) {
+            Some((_, default)) => *default,
+            None => self.default,
+        }
+    }
+}
+
+/// Leaf field resolved against a loader prefix and separator.
+pub(crate) struct Leaf<'a> {
+    pub(crate) key: String,
+    pub(crate) env: String,
+    pub(crate) field: &'a Field,
+}
+
+pub(crate) fn leaves<'a>(fields: &'a [Field], prefix: &str, separator: &str) -> Vec<Leaf<'a>> {
+    fn walk<'a>(
+        fields: &'a [Field],
+        keys: &mut Vec<&'a str>,
+        segments: &mut Vec<String>,
+        separator: &str,
+        leaves: &mut Vec<Leaf<'a>>,
+    ) {
+        for field in fields {
+            keys.push(field.name);
+            segments.push(match field.env_key {
+                "" => field.name.to_ascii_uppercase(),
+                env_key => env_key.to_string(),
+            });
+
+            match &field.nested {
+                Some(nested) => walk(nested, keys, segments, separator, leaves),
+                None => leaves.push(Leaf {
+                    key: keys.join("."),
+                    env: match field.env {
+                        Some(env) => env.to_string(),
+                        None => segments.join(separator),
+                    },
+                    field,
+                }),
+            }
+
+            keys.pop();
+            segments.pop();
+        }
+    }
+
+    let mut segments = Vec::new();
+    if !prefix.is_empty() {
+        segments.push(prefix.to_string());
+    }
+
+    let mut leaves = Vec::new();
+    walk(fields, &mut Vec::new(), &mut segments, separator, &mut leaves);
+
+    leaves
+}
+
+/// Converts a typed default value into a configuration value.
+pub fn to_value<T: Serialize>(value: &T) -> Result<Value, Error> {
+    let value = serde_json::to_value(value).map_err(|v| ConfigError::Foreign(Box::new(v)))?;
+
+    Ok(from_json(value))
+}
+
+fn from_json(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::from(ValueKind::Nil),
+        serde_json::Value::Bool(value) => Value::from(value),
+        serde_json::Value::Number(value) => match (value.as_i64(), value.as_u64()) {
+            (Some(value), _) => Value::from(value),
+            (None, Some(value)) => Value::from(value),
+            (None, None) => Value::from(value.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(value) => Value::from(value),
+        serde_json::Value::Array(values) => {
+            Value::from(values.into_iter().map(from_json).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(values) => Value::from(
+            values
+                .into_iter()
+                .map(|(key, value)| (key, from_json(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
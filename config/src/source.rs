@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use config_driver::{ConfigError, Map, Source, Value};
+use errors::prelude::*;
+
+/// Pluggable configuration source (key-value store, mounted directory...).
+///
+/// Values are keyed by field key, nested fields using dots (e.g. `section.field`).
+pub trait ConfigSource: Send + Sync {
+    /// Name of the source, reported as the origin of its values.
+    fn name(&self) -> String;
+
+    fn collect(&self) -> Result<HashMap<String, Value>>;
+}
+
+/// Configuration source loaded asynchronously (HTTP endpoint, remote key-value store...), only
+/// supported by `Loader::load_async`.
+#[async_trait]
+pub trait AsyncConfigSource: Send + Sync {
+    /// Name of the source, reported as the origin of its values.
+    fn name(&self) -> String;
+
+    async fn collect(&self) -> Result<HashMap<String, Value>>;
+}
+
+/// In-memory source, e.g. standing in for a key-value store.
+#[derive(Clone, Debug)]
+pub struct MapSource {
+    name: String,
+    values: HashMap<String, Value>,
+}
+
+impl MapSource {
+    pub fn new(name: &str) -> Self {
+        MapSource {
+            name: name.to_string(),
+            values: HashMap::new(),
+        }
+    }
+
+    pub fn with<V: Into<Value>>(mut self, key: &str, value: V) -> Self {
+        self.values.insert(key.to_string(), value.into());
+        self
+    }
+}
+
+impl ConfigSource for MapSource {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn collect(&self) -> Result<HashMap<String, Value>> {
+        Ok(self.values.clone())
+    }
+}
+
+/// Directory holding one file per field, named after the field key (e.g. a mounted Kubernetes
+/// ConfigMap). Hidden entries, such as the `..data` links maintained by Kubernetes, are skipped.
+#[derive(Clone, Debug)]
+pub struct DirectorySource {
+    path: PathBuf,
+    required: bool,
+}
+
+impl DirectorySource {
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        DirectorySource {
+            path: path.as_ref().to_path_buf(),
+            required: true,
+        }
+    }
+
+    /// Sets whether a missing directory is an error (defaults to `true`).
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+}
+
+impl ConfigSource for DirectorySource {
+    fn name(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    fn collect(&self) -> Result<HashMap<String, Value>> {
+        let mut values = HashMap::new();
+
+        if !self.required && !self.path.exists() {
+            return Ok(values);
+        }
+
+        let entries = fs::read_dir(&self.path)
+            .with_context(|| format!("failed to read directory {}", self.path.display()))?;
+
+        for entry in entries {
+            let path = entry?.path();
+
+            let Some(key) = path.file_name().and_then(|v| v.to_str()) else {
+                continue;
+            };
+
+            if key.starts_with('.') || !path.is_file() {
+                continue;
+            }
+
+            let value =
+                fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+
+            values.insert(
+                key.to_string(),
+                Value::from(value.trim_end_matches(['\r', '\n']).to_string()),
+            );
+        }
+
+        Ok(values)
+    }
+}
+
+/// Values collected from a pluggable source, fed to the configuration builder.
+#[derive(Clone, Debug)]
+pub(crate) struct Collected {
+    pub(crate) name: String,
+    pub(crate) values: HashMap<String, Value>,
+}
+
+impl Source for Collected {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ConfigError> {
+        Ok(self
+            .values
+            .iter()
+            .map(|(key, value)| (key.clone(), Value::new(Some(&self.name), value.kind.clone())))
+            .collect())
+    }
+}
@@ -0,0 +1,159 @@
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+const UNITS: &[(&str, u64)] = &[
+    ("TiB", 1 << 40),
+    ("GiB", 1 << 30),
+    ("MiB", 1 << 20),
+    ("KiB", 1 << 10),
+    ("TB", 1_000_000_000_000),
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+/// Byte size loaded from human-readable strings such as `512KiB`, `10MB` or `1.5GiB` (bare numbers
+/// being read as bytes).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(value: u64) -> Self {
+        ByteSize(value)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(value: ByteSize) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        let end = value
+            .find(|v: char| !v.is_ascii_digit() && v != '.')
+            .unwrap_or(value.len());
+        let (number, unit) = value.split_at(end);
+
+        let factor = match unit.trim() {
+            "" => 1,
+            unit => UNITS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(unit))
+                .map(|(_, factor)| *factor)
+                .ok_or_else(|| format!("invalid byte size: {:?}", s))?,
+        };
+
+        if let Ok(number) = number.parse::<u64>() {
+            return number
+                .checked_mul(factor)
+                .map(ByteSize)
+                .ok_or_else(|| format!("byte size overflow: {:?}", s));
+        }
+
+        match number.parse::<f64>() {
+            Ok(number) if number >= 0.0 && number * (factor as f64) < u64::MAX as f64 => {
+                Ok(ByteSize((number * factor as f64).round() as u64))
+            }
+            _ => Err(format!("invalid byte size: {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (unit, factor) = UNITS
+            .iter()
+            .find(|(_, factor)| self.0 != 0 && self.0.is_multiple_of(*factor))
+            .unwrap_or(&("B", 1));
+
+        write!(f, "{}{}", self.0 / factor, unit)
+    }
+}
+
+impl Serialize for ByteSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = ByteSize;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a byte size (e.g. 512KiB, 10MB)")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(ByteSize(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v)
+                    .map(ByteSize)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_byte_sizes() {
+        assert_eq!("512".parse(), Ok(ByteSize(512)));
+        assert_eq!("512KiB".parse(), Ok(ByteSize(512 << 10)));
+        assert_eq!("10MB".parse(), Ok(ByteSize(10_000_000)));
+        assert_eq!("10 mb".parse(), Ok(ByteSize(10_000_000)));
+        assert_eq!("1.5GiB".parse(), Ok(ByteSize(3 << 29)));
+    }
+
+    #[test]
+    fn rejects_invalid_byte_sizes() {
+        assert!("".parse::<ByteSize>().is_err());
+        assert!("KiB".parse::<ByteSize>().is_err());
+        assert!("10XB".parse::<ByteSize>().is_err());
+        assert!("1.2.3MB".parse::<ByteSize>().is_err());
+        assert!("-1MB".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_byte_sizes() {
+        assert!("18446744073709551615B".parse::<ByteSize>().is_ok());
+        assert!("18446744073709551616".parse::<ByteSize>().is_err());
+        assert!("20000000TiB".parse::<ByteSize>().is_err());
+        assert!("99999999999.5TiB".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn displays_largest_exact_unit() {
+        assert_eq!(ByteSize(3 << 30).to_string(), "3GiB");
+        assert_eq!(ByteSize(10_000_000).to_string(), "10MB");
+        assert_eq!(ByteSize(1_500).to_string(), "1500B");
+        assert_eq!(ByteSize(0).to_string(), "0B");
+    }
+}
@@ -0,0 +1,215 @@
+use std::{fmt, ops::Deref, str::FromStr, time};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+const UNITS: &[(&str, u128)] = &[
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+/// Duration loaded from human-readable strings such as `5s`, `250ms` or `1h30m` (bare numbers being
+/// read as seconds).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Duration(time::Duration);
+
+impl Duration {
+    pub const fn from_secs(secs: u64) -> Self {
+        Duration(time::Duration::from_secs(secs))
+    }
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Duration(time::Duration::from_millis(millis))
+    }
+}
+
+impl Deref for Duration {
+    type Target = time::Duration;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(value: time::Duration) -> Self {
+        Duration(value)
+    }
+}
+
+impl From<Duration> for time::Duration {
+    fn from(value: Duration) -> Self {
+        value.0
+    }
+}
+
+impl FromStr for Duration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err("invalid duration: empty value".to_string());
+        }
+
+        if let Ok(secs) = s.parse::<u64>() {
+            return Ok(Duration::from_secs(secs));
+        }
+
+        let mut nanos: u128 = 0;
+        let mut rest = s;
+
+        while !rest.is_empty() {
+            let end = rest.find(|v: char| !v.is_ascii_digit()).unwrap_or(rest.len());
+            let (number, tail) = rest.split_at(end);
+
+            let unit_end = tail.find(|v: char| v.is_ascii_digit()).unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_end);
+
+            let factor = match unit.trim() {
+                "µs" => 1_000,
+                unit => UNITS
+                    .iter()
+                    .find(|(name, _)| *name == unit)
+                    .map(|(_, factor)| *factor)
+                    .ok_or_else(|| format!("invalid duration: {:?}", s))?,
+            };
+
+            if number.is_empty() {
+                return Err(format!("invalid duration: {:?}", s));
+            }
+
+            nanos = number
+                .parse::<u128>()
+                .ok()
+                .and_then(|v| v.checked_mul(factor))
+                .and_then(|v| v.checked_add(nanos))
+                .ok_or_else(|| format!("duration overflow: {:?}", s))?;
+            rest = tail.trim_start();
+        }
+
+        let secs = u64::try_from(nanos / 1_000_000_000).map_err(|_| format!("duration overflow: {:?}", s))?;
+
+        Ok(Duration(time::Duration::new(
+            secs,
+            (nanos % 1_000_000_000) as u32,
+        )))
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nanos = self.0.as_nanos();
+        if nanos == 0 {
+            return f.write_str("0s");
+        }
+
+        for (unit, factor) in UNITS {
+            if nanos >= *factor {
+                write!(f, "{}{}", nanos / factor, unit)?;
+                nanos %= factor;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl de::Visitor<'_> for Visitor {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a duration (e.g. 5s, 250ms, 1h30m)")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(Duration::from_secs(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u64::try_from(v)
+                    .map(Duration::from_secs)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                time::Duration::try_from_secs_f64(v)
+                    .map(Duration)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Float(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!("5".parse(), Ok(Duration::from_secs(5)));
+        assert_eq!("250ms".parse(), Ok(Duration::from_millis(250)));
+        assert_eq!("1h30m".parse(), Ok(Duration::from_secs(5_400)));
+        assert_eq!("1m 30s".parse(), Ok(Duration::from_secs(90)));
+        assert_eq!("2d".parse(), Ok(Duration::from_secs(172_800)));
+        assert_eq!(
+            "15µs".parse(),
+            Ok(Duration::from(time::Duration::from_micros(15)))
+        );
+        assert_eq!(
+            "15us".parse(),
+            Ok(Duration::from(time::Duration::from_micros(15)))
+        );
+        assert_eq!("10ns".parse(), Ok(Duration::from(time::Duration::from_nanos(10))));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert!("".parse::<Duration>().is_err());
+        assert!("  ".parse::<Duration>().is_err());
+        assert!("5w".parse::<Duration>().is_err());
+        assert!("ms".parse::<Duration>().is_err());
+        assert!("1h30".parse::<Duration>().is_err());
+        assert!("-5s".parse::<Duration>().is_err());
+    }
+
+    #[test]
+    fn rejects_overflowing_durations() {
+        for value in [
+            "99999999999999999999999999d",
+            "99999999999999999999999999999999999999d",
+            "999999999999999999999999999999999999999999s",
+            "213503982334602d",
+        ] {
+            let err = value.parse::<Duration>().unwrap_err();
+            assert!(err.starts_with("duration overflow"), "{}: {}", value, err);
+        }
+    }
+
+    #[test]
+    fn displays_compound_durations() {
+        assert_eq!(Duration::from_secs(90).to_string(), "1m30s");
+        assert_eq!(Duration::from_millis(1_250).to_string(), "1s250ms");
+        assert_eq!(Duration::default().to_string(), "0s");
+    }
+}
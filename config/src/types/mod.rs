@@ -0,0 +1,37 @@
+pub use byte_size::ByteSize;
+pub use duration::Duration;
+pub use url::Url;
+
+mod byte_size;
+mod duration;
+
+/// Implements `Serialize` and `Deserialize` for a type through its `Display` and `FromStr`
+/// implementations, which allows loading enums and other parsed values from plain strings.
+#[macro_export]
+macro_rules! from_str_serde {
+    ($ty:ty) => {
+        impl $crate::__internal::serde::Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__internal::serde::Serializer,
+            {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> $crate::__internal::serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: $crate::__internal::serde::Deserializer<'de>,
+            {
+                let value = <::std::string::String as $crate::__internal::serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+
+                value
+                    .parse()
+                    .map_err(<D::Error as $crate::__internal::serde::de::Error>::custom)
+            }
+        }
+    };
+}
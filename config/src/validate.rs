@@ -0,0 +1,122 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Debug,
+};
+
+use regex::Regex;
+
+/// Collects field violations while walking a configuration value.
+#[derive(Debug, Default)]
+pub struct Violations {
+    path: Vec<&'static str>,
+    items: Vec<(Vec<&'static str>, String)>,
+}
+
+impl Violations {
+    pub fn check(&mut self, field: &'static str, result: Result<(), String>) {
+        if let Err(message) = result {
+            let mut path = self.path.clone();
+            path.push(field);
+
+            self.items.push((path, message));
+        }
+    }
+
+    pub fn nested<F: FnOnce(&mut Self)>(&mut self, field: &'static str, f: F) {
+        self.path.push(field);
+        f(self);
+        self.path.pop();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub(crate) fn into_items(self) -> Vec<(Vec<&'static str>, String)> {
+        self.items
+    }
+}
+
+pub fn range<T: PartialOrd + Debug>(value: &T, min: Option<T>, max: Option<T>) -> Result<(), String> {
+    match (min, max) {
+        (Some(min), _) if *value < min => Err(format!("must be greater than or equal to {:?}", min)),
+        (_, Some(max)) if *value > max => Err(format!("must be less than or equal to {:?}", max)),
+        _ => Ok(()),
+    }
+}
+
+pub fn one_of<T, U>(value: &T, values: &[U]) -> Result<(), String>
+where
+    T: PartialEq<U>,
+    U: Debug,
+{
+    if values.iter().any(|v| value == v) {
+        Ok(())
+    } else {
+        Err(format!("must be one of {:?}", values))
+    }
+}
+
+pub fn regex<T: AsRef<str>>(value: &T, pattern: &str) -> Result<(), String> {
+    let regex = Regex::new(pattern).map_err(|v| format!("invalid pattern: {}", v))?;
+
+    if regex.is_match(value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!("must match {:?}", pattern))
+    }
+}
+
+pub fn non_empty<T: IsEmpty + ?Sized>(value: &T) -> Result<(), String> {
+    if value.is_empty() {
+        Err("must not be empty".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+pub trait IsEmpty {
+    fn is_empty(&self) -> bool;
+}
+
+impl IsEmpty for str {
+    fn is_empty(&self) -> bool {
+        str::is_empty(self)
+    }
+}
+
+impl IsEmpty for String {
+    fn is_empty(&self) -> bool {
+        String::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for Vec<T> {
+    fn is_empty(&self) -> bool {
+        Vec::is_empty(self)
+    }
+}
+
+impl<K, V> IsEmpty for HashMap<K, V> {
+    fn is_empty(&self) -> bool {
+        HashMap::is_empty(self)
+    }
+}
+
+impl<K, V> IsEmpty for BTreeMap<K, V> {
+    fn is_empty(&self) -> bool {
+        BTreeMap::is_empty(self)
+    }
+}
+
+impl<T> IsEmpty for HashSet<T> {
+    fn is_empty(&self) -> bool {
+        HashSet::is_empty(self)
+    }
+}
+
+impl<T: IsEmpty> IsEmpty for Option<T> {
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_none_or(IsEmpty::is_empty)
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "cli")]
+
+use std::collections::{BTreeMap, HashSet};
+
+use config::{prelude::*, FieldKind, Loader, ScalarKind};
+use serde::Deserialize;
+
+type Hosts = Vec<String>;
+
+#[derive(Config, Deserialize)]
+struct App {
+    #[config(default)]
+    verbose: std::primitive::bool,
+    debug: Option<bool>,
+    #[config(default)]
+    ports: std::vec::Vec<u16>,
+    tags: Option<Vec<String>>,
+    #[config(default)]
+    roles: HashSet<String>,
+    #[config(default, list_separator = ",")]
+    hosts: Hosts,
+    #[config(default)]
+    limits: BTreeMap<String, f64>,
+}
+
+#[test]
+fn derives_field_kinds() {
+    let kinds: Vec<(&str, FieldKind)> = App::fields().iter().map(|v| (v.name, v.kind)).collect();
+
+    assert_eq!(
+        kinds,
+        [
+            ("verbose", FieldKind::Scalar(ScalarKind::Bool)),
+            ("debug", FieldKind::Scalar(ScalarKind::Bool)),
+            ("ports", FieldKind::List(ScalarKind::Unsigned)),
+            ("tags", FieldKind::List(ScalarKind::Other)),
+            ("roles", FieldKind::List(ScalarKind::Other)),
+            ("hosts", FieldKind::List(ScalarKind::Other)),
+            ("limits", FieldKind::Map(ScalarKind::Float)),
+        ]
+    );
+}
+
+#[test]
+fn parses_flags_by_field_kind() {
+    let app = Loader::<App>::new("APP")
+        .with_env(std::iter::empty::<(&str, &str)>())
+        .with_args([
+            "app",
+            "--verbose",
+            "--debug",
+            "false",
+            "--ports",
+            "80",
+            "--ports",
+            "443",
+            "--tags",
+            "a",
+            "--roles",
+            "admin",
+            "--roles",
+            "admin",
+            "--hosts",
+            "a,b",
+            "--hosts",
+            "c",
+            "--limits",
+            "cpu=0.5",
+        ])
+        .unwrap()
+        .load()
+        .unwrap();
+
+    assert!(app.verbose);
+    assert_eq!(app.debug, Some(false));
+    assert_eq!(app.ports, [80, 443]);
+    assert_eq!(app.tags, Some(vec!["a".to_string()]));
+    assert_eq!(app.roles, HashSet::from(["admin".to_string()]));
+    assert_eq!(app.hosts, ["a", "b", "c"]);
+    assert_eq!(app.limits["cpu"], 0.5);
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeMap;
+
+use config::{prelude::*, types::Duration, Format, Loader, Secret};
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+#[allow(dead_code)]
+struct Database {
+    /// Connection URL.
+    #[config(default = "postgres://localhost")]
+    url: String,
+    #[config(default = "hunter2")]
+    password: Secret<String>,
+    #[config(default = 10)]
+    pool_size: u32,
+}
+
+#[derive(Config, Deserialize)]
+#[allow(dead_code)]
+struct App {
+    #[config(default = 8080)]
+    port: u16,
+    #[config(default = false)]
+    verbose: bool,
+    #[config(default = 0.5)]
+    ratio: f64,
+    #[config(default = "5s")]
+    timeout: Duration,
+    #[config(default, list_separator = ",")]
+    ports: Vec<u16>,
+    #[config(default, map)]
+    limits: BTreeMap<String, i64>,
+    name: Option<String>,
+    #[config(nested)]
+    database: Database,
+}
+
+fn loader() -> Loader<App> {
+    Loader::new("APP").with_env([
+        ("APP_PORT", "9090"),
+        ("APP_VERBOSE", "true"),
+        ("APP_RATIO", "0.25"),
+        ("APP_PORTS", "80,443"),
+        ("APP_LIMITS", "cpu=2"),
+        ("APP_DATABASE_PASSWORD", "s3cret"),
+        ("APP_DATABASE_POOL_SIZE", "20"),
+    ])
+}
+
+#[test]
+fn dumps_effective_configuration() {
+    let (_, resolution) = loader().resolve().unwrap();
+
+    assert_eq!(
+        resolution.dump(Format::Toml).unwrap(),
+        r#"port = 9090
+verbose = true
+ratio = 0.25
+timeout = "5s"
+ports = [
+    80,
+    443,
+]
+
+[limits]
+cpu = 2
+
+[database]
+url = "postgres://localhost"
+password = "[REDACTED]"
+pool_size = 20
+"#
+    );
+
+    assert_eq!(
+        resolution.dump(Format::Yaml).unwrap(),
+        r#"port: 9090
+verbose: true
+ratio: 0.25
+timeout: 5s
+ports:
+- 80
+- 443
+limits:
+  cpu: 2
+database:
+  url: postgres://localhost
+  password: '[REDACTED]'
+  pool_size: 20
+"#
+    );
+
+    assert_eq!(
+        resolution.dump(Format::Json).unwrap(),
+        r#"{
+  "port": 9090,
+  "verbose": true,
+  "ratio": 0.25,
+  "timeout": "5s",
+  "ports": [
+    80,
+    443
+  ],
+  "limits": {
+    "cpu": 2
+  },
+  "database": {
+    "url": "postgres://localhost",
+    "password": "[REDACTED]",
+    "pool_size": 20
+  }
+}
+"#
+    );
+
+    assert_eq!(
+        resolution.dump(Format::Dotenv).unwrap(),
+        "APP_PORT=9090
+APP_VERBOSE=true
+APP_RATIO=0.25
+APP_TIMEOUT=5s
+APP_PORTS=80,443
+APP_LIMITS=cpu=2
+# APP_NAME=
+APP_DATABASE_URL=postgres://localhost
+APP_DATABASE_PASSWORD=[REDACTED]
+APP_DATABASE_POOL_SIZE=20
+"
+    );
+}
+
+#[test]
+fn dumps_default_configuration() {
+    let description = loader().describe().unwrap();
+
+    assert_eq!(
+        description.dump(Format::Toml).unwrap(),
+        r#"port = 8080
+verbose = false
+ratio = 0.5
+timeout = "5s"
+ports = []
+
+[limits]
+
+[database]
+url = "postgres://localhost"
+password = "[REDACTED]"
+pool_size = 10
+"#
+    );
+
+    assert_eq!(
+        description.dump(Format::Json).unwrap(),
+        r#"{
+  "port": 8080,
+  "verbose": false,
+  "ratio": 0.5,
+  "timeout": "5s",
+  "ports": [],
+  "limits": {},
+  "database": {
+    "url": "postgres://localhost",
+    "password": "[REDACTED]",
+    "pool_size": 10
+  }
+}
+"#
+    );
+
+    let yaml = description.dump(Format::Yaml).unwrap();
+    assert!(yaml.contains("database:\n  url: postgres://localhost\n  password: '[REDACTED]'\n"));
+
+    let dotenv = description.dump(Format::Dotenv).unwrap();
+    assert!(dotenv.contains("# Connection URL.\n# Type: String\nAPP_DATABASE_URL=postgres://localhost\n"));
+    assert!(dotenv.contains("# APP_DATABASE_PASSWORD=\n"));
+    assert!(!dotenv.contains("hunter2"));
+}
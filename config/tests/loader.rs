@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use config::{prelude::*, types::Duration, Error, Loader};
+use serde::Deserialize;
+
+#[derive(Config, Deserialize, Debug)]
+#[allow(dead_code)]
+struct App {
+    #[config(default = "a", one_of("a", "b"))]
+    letter: String,
+    #[config(default = "5s")]
+    timeout: Duration,
+    #[config(default, map)]
+    headers: HashMap<String, String>,
+    #[config(default, list_separator = ",")]
+    hosts: Vec<String>,
+}
+
+#[derive(Config, Deserialize, Debug)]
+struct Database {
+    #[config(default = "localhost")]
+    host: String,
+    #[config(default = 4)]
+    pool_size: u32,
+}
+
+#[derive(Config, Deserialize, Debug)]
+struct Server {
+    #[config(default = 8080)]
+    port: u16,
+    #[config(nested)]
+    database: Database,
+}
+
+fn errors(env: &[(&str, &str)]) -> Vec<(String, String)> {
+    match Loader::<App>::new("APP").with_env(env.iter().copied()).load() {
+        Err(Error::Validation(errors)) => errors.into_iter().map(|v| (v.env, v.key)).collect(),
+        result => panic!("expected validation errors, got {:?}", result),
+    }
+}
+
+#[test]
+fn reports_every_failing_field() {
+    let expected = |env: &str, key: &str| (env.to_string(), key.to_string());
+
+    assert_eq!(
+        errors(&[("APP_LETTER", "z"), ("APP_TIMEOUT", "5x")]),
+        [
+            expected("APP_TIMEOUT", "timeout"),
+            expected("APP_LETTER", "letter")
+        ]
+    );
+    assert_eq!(
+        errors(&[("APP_LETTER", "z"), ("APP_TIMEOUT", "5x"), ("APP_HEADERS", "A")]),
+        [
+            expected("APP_HEADERS", "headers"),
+            expected("APP_TIMEOUT", "timeout"),
+            expected("APP_LETTER", "letter")
+        ]
+    );
+}
+
+#[test]
+fn parses_lists_and_maps() {
+    let app = Loader::<App>::new("APP")
+        .with_env([("APP_HEADERS", "X-Auth = 1, X-Trace=2"), ("APP_HOSTS", "a, b,,c")])
+        .load()
+        .unwrap();
+
+    assert_eq!(app.headers["x-auth"], "1");
+    assert_eq!(app.headers["x-trace"], "2");
+    assert_eq!(app.hosts, ["a", "b", "c"]);
+}
+
+#[test]
+fn loads_nested_fields() {
+    let server = Loader::<Server>::new("APP")
+        .with_env(std::iter::empty::<(&str, &str)>())
+        .load()
+        .unwrap();
+
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.database.host, "localhost");
+    assert_eq!(server.database.pool_size, 4);
+
+    let server = Loader::<Server>::new("APP")
+        .with_env([("APP_DATABASE_POOL_SIZE", "16")])
+        .load()
+        .unwrap();
+
+    assert_eq!(server.database.host, "localhost");
+    assert_eq!(server.database.pool_size, 16);
+
+    let server = Loader::<Server>::new("APP")
+        .with_separator("__")
+        .with_env([
+            ("APP__PORT", "9090"),
+            ("APP__DATABASE__HOST", "db"),
+            ("APP_DATABASE_POOL_SIZE", "16"),
+        ])
+        .load()
+        .unwrap();
+
+    assert_eq!(server.port, 9090);
+    assert_eq!(server.database.host, "db");
+    assert_eq!(server.database.pool_size, 4);
+
+    match Loader::<Server>::new("APP")
+        .with_separator("__")
+        .with_env([("APP__DATABASE__POOL_SIZE", "many")])
+        .load()
+    {
+        Err(Error::Validation(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].key, "database.pool_size");
+            assert_eq!(errors[0].env, "APP__DATABASE__POOL_SIZE");
+        }
+        result => panic!("expected validation errors, got {:?}", result),
+    }
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use config::{prelude::*, Loader, MapSource, Origin, Resolution};
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+#[allow(dead_code)]
+struct App {
+    #[config(default = 8080)]
+    port: u16,
+    #[config(default = "console", profile(production = "otlp"))]
+    exporter: String,
+    #[config(default = "info")]
+    level: String,
+    #[config(default, map)]
+    headers: HashMap<String, String>,
+    #[config(default = "localhost")]
+    host: String,
+    #[config(default = "us-east-1")]
+    region: String,
+    #[config(default = 4)]
+    workers: u32,
+    #[config(default = false)]
+    verbose: bool,
+    name: Option<String>,
+    #[config(default = 30, alias = "APP_TIMEOUT_SECS")]
+    timeout: u32,
+}
+
+fn file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}.toml", name, std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn origin(resolution: &Resolution, key: &str) -> Origin {
+    resolution.get(key).unwrap().origin.clone()
+}
+
+#[test]
+fn reports_every_origin() {
+    let path = file("provenance", "host = \"example.com\"\n");
+
+    let loader = Loader::<App>::new("APP")
+        .with_profile("production")
+        .with_file(&path)
+        .with_source(MapSource::new("vault").with("region", "eu-west-1"))
+        .with_env([("APP_LEVEL", "debug"), ("APP_HEADERS", "X-Auth=1")])
+        .with_override("workers", 8);
+
+    #[cfg(feature = "cli")]
+    let loader = loader.with_args(["app", "--verbose"]).unwrap();
+
+    let (_, resolution) = loader.resolve().unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(origin(&resolution, "port"), Origin::Default);
+    assert_eq!(
+        origin(&resolution, "exporter"),
+        Origin::Profile("production".into())
+    );
+    assert_eq!(
+        origin(&resolution, "level"),
+        Origin::Env {
+            name: "APP_LEVEL".into(),
+            deprecated: false
+        }
+    );
+    assert_eq!(
+        origin(&resolution, "headers"),
+        Origin::Env {
+            name: "APP_HEADERS".into(),
+            deprecated: false
+        }
+    );
+    assert!(matches!(
+        origin(&resolution, "host"),
+        Origin::File { path: found, key } if path.file_name() == Path::new(&found).file_name() && key == "host"
+    ));
+    assert_eq!(origin(&resolution, "region"), Origin::Source("vault".into()));
+    assert_eq!(origin(&resolution, "workers"), Origin::Override);
+    #[cfg(feature = "cli")]
+    assert_eq!(origin(&resolution, "verbose"), Origin::Arg("--verbose".into()));
+    assert_eq!(origin(&resolution, "name"), Origin::Unset);
+}
+
+#[test]
+fn reports_map_entries_origin() {
+    let path = file("provenance-map", "[headers]\nx-trace = \"1\"\n");
+
+    let (_, resolution) = Loader::<App>::new("APP")
+        .with_file(&path)
+        .with_env([("APP_HEADERS", "X-Auth=1")])
+        .resolve()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let headers = resolution.get("headers").unwrap();
+    assert_eq!(
+        headers.origin,
+        Origin::Env {
+            name: "APP_HEADERS".into(),
+            deprecated: false
+        }
+    );
+    let mut entries: Vec<&str> = headers.value.as_deref().unwrap().split(',').collect();
+    entries.sort();
+    assert_eq!(entries, ["x-auth=1", "x-trace=1"]);
+
+    let (_, resolution) = Loader::<App>::new("APP")
+        .with_env([("APP_LEVEL", "debug")])
+        .resolve()
+        .unwrap();
+    assert_eq!(origin(&resolution, "headers"), Origin::Default);
+}
+
+#[test]
+fn reports_deprecated_aliases() {
+    let (_, resolution) = Loader::<App>::new("APP")
+        .with_env([("APP_TIMEOUT_SECS", "10")])
+        .resolve()
+        .unwrap();
+
+    let timeout = resolution.get("timeout").unwrap();
+    assert_eq!(
+        timeout.origin,
+        Origin::Env {
+            name: "APP_TIMEOUT_SECS".into(),
+            deprecated: true
+        }
+    );
+    assert_eq!(timeout.origin.to_string(), "env APP_TIMEOUT_SECS (deprecated)");
+
+    let (_, resolution) = Loader::<App>::new("APP")
+        .with_env([("APP_TIMEOUT", "20"), ("APP_TIMEOUT_SECS", "10")])
+        .resolve()
+        .unwrap();
+
+    assert_eq!(
+        origin(&resolution, "timeout"),
+        Origin::Env {
+            name: "APP_TIMEOUT".into(),
+            deprecated: false
+        }
+    );
+}
+
+#[test]
+fn logs_deprecated_aliases() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || Writer(writer.clone()))
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, || {
+        Loader::<App>::new("APP")
+            .with_env([("APP_TIMEOUT_SECS", "10")])
+            .load()
+            .unwrap();
+    });
+
+    let logs = String::from_utf8(logs.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("WARN"), "{}", logs);
+    assert!(
+        logs.contains("environment variable APP_TIMEOUT_SECS is deprecated, use APP_TIMEOUT instead"),
+        "{}",
+        logs
+    );
+}
+
+struct Writer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "reload")]
+
+use std::{
+    fs::{self, File},
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use config::{prelude::*, Loader};
+use serde::Deserialize;
+
+#[derive(Config, Deserialize, Debug)]
+struct App {
+    #[config(default = 8080)]
+    port: u16,
+}
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn write(path: &Path, content: &str, modified: SystemTime) {
+    fs::write(path, content).unwrap();
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(modified)
+        .unwrap();
+}
+
+#[test]
+fn reloads_changed_files() {
+    let path = std::env::temp_dir().join(format!("reload-{}.toml", std::process::id()));
+    let start = SystemTime::now() - Duration::from_secs(60);
+    write(&path, "port = 1\n", start);
+
+    let handle = Loader::<App>::new("APP")
+        .with_file(&path)
+        .with_env(std::iter::empty::<(&str, &str)>())
+        .watch(Duration::from_millis(10))
+        .unwrap();
+    let reloads = handle.subscribe();
+    assert_eq!(handle.current().port, 1);
+
+    write(&path, "port = 2\n", start + Duration::from_secs(1));
+    let app = reloads.recv_timeout(TIMEOUT).unwrap().unwrap();
+    assert_eq!(app.port, 2);
+    assert_eq!(handle.current().port, 2);
+
+    write(&path, "port = \"invalid\"\n", start + Duration::from_secs(2));
+    assert!(reloads.recv_timeout(TIMEOUT).unwrap().is_err());
+    assert_eq!(handle.current().port, 2);
+
+    fs::remove_file(&path).unwrap();
+}
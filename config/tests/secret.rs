@@ -0,0 +1,59 @@
+use std::fs;
+
+use config::{prelude::*, Format, Loader, Secret};
+use serde::Deserialize;
+
+fn default_token() -> Secret<String> {
+    Secret::new("token".to_string())
+}
+
+#[derive(Config, Deserialize)]
+struct App {
+    #[config(secret, default = "changeme")]
+    password: Secret<String>,
+    #[config(default = Secret::new("key".to_string()))]
+    key: Secret<String>,
+    #[config(default_fn = default_token)]
+    token: Secret<String>,
+}
+
+#[test]
+fn reads_secrets_from_files() {
+    let path = std::env::temp_dir().join(format!("secret-{}", std::process::id()));
+    fs::write(&path, "hunter2\r\n").unwrap();
+
+    let app = Loader::<App>::new("APP")
+        .with_env([("APP_PASSWORD_FILE", path.to_str().unwrap())])
+        .load()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(app.password.expose(), "hunter2");
+    assert_eq!(app.key.expose(), "key");
+    assert_eq!(app.token.expose(), "token");
+
+    let app = Loader::<App>::new("APP")
+        .with_env([("APP_PASSWORD", "plain"), ("APP_PASSWORD_FILE", "/nonexistent")])
+        .load()
+        .unwrap();
+    assert_eq!(app.password.expose(), "plain");
+
+    assert!(Loader::<App>::new("APP")
+        .with_env([("APP_PASSWORD_FILE", "/nonexistent")])
+        .load()
+        .is_err());
+}
+
+#[test]
+fn redacts_secret_defaults() {
+    let (_, resolution) = Loader::<App>::new("APP")
+        .with_env([("APP_KEY", "k")])
+        .resolve()
+        .unwrap();
+
+    assert_eq!(
+        resolution.dump(Format::Dotenv).unwrap(),
+        "APP_PASSWORD=[REDACTED]\nAPP_KEY=[REDACTED]\nAPP_TOKEN=[REDACTED]\n"
+    );
+    assert_eq!(format!("{:?}", Secret::new("k")), "[REDACTED]");
+}
@@ -0,0 +1,28 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+fn port() -> u16 {
+    8080
+}
+
+#[derive(Config, Deserialize)]
+struct Inner {
+    name: String,
+}
+
+#[derive(Config, Deserialize)]
+struct Config {
+    #[config(default = 80, default_fn = port)]
+    port: u16,
+
+    #[config(regex = "[a-z")]
+    name: String,
+
+    #[config(one_of(allowed = "a"))]
+    mode: String,
+
+    #[config(nested, default)]
+    inner: Inner,
+}
+
+fn main() {}
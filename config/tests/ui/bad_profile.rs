@@ -0,0 +1,10 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+struct Config {
+    #[config(default = "console", profile("otlp"))]
+    exporter: String,
+}
+
+fn main() {}
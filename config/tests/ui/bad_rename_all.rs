@@ -0,0 +1,10 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+#[config(rename_all = "SHOUTING_CASE")]
+struct Config {
+    exporter: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+struct Config {
+    #[config(secret)]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+struct Config {
+    #[config(default = 0.5)]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+enum Exporter {
+    Console,
+    Otlp,
+}
+
+fn main() {}
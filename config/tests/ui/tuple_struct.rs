@@ -0,0 +1,7 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+struct Config(String, u16);
+
+fn main() {}
@@ -0,0 +1,13 @@
+use config::prelude::*;
+use serde::Deserialize;
+
+#[derive(Config, Deserialize)]
+struct Config {
+    #[config(defualt = "console")]
+    exporter: String,
+
+    #[config(non_empty, sercet)]
+    token: String,
+}
+
+fn main() {}
@@ -0,0 +1,260 @@
+use std::{collections::BTreeMap, fmt};
+
+use http::StatusCode;
+use thiserror::Error;
+
+use crate::prelude::AnyError;
+
+/// Error code, following the gRPC status codes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Code {
+    Cancelled,
+    Unknown,
+    InvalidArgument,
+    DeadlineExceeded,
+    NotFound,
+    AlreadyExists,
+    PermissionDenied,
+    ResourceExhausted,
+    FailedPrecondition,
+    Aborted,
+    OutOfRange,
+    Unimplemented,
+    Internal,
+    Unavailable,
+    DataLoss,
+    Unauthenticated,
+}
+
+impl Code {
+    /// Returns the code matching a gRPC status code, `None` for `0` (ok) and unknown codes.
+    pub fn from_grpc(code: i32) -> Option<Self> {
+        match code {
+            1 => Some(Code::Cancelled),
+            2 => Some(Code::Unknown),
+            3 => Some(Code::InvalidArgument),
+            4 => Some(Code::DeadlineExceeded),
+            5 => Some(Code::NotFound),
+            6 => Some(Code::AlreadyExists),
+            7 => Some(Code::PermissionDenied),
+            8 => Some(Code::ResourceExhausted),
+            9 => Some(Code::FailedPrecondition),
+            10 => Some(Code::Aborted),
+            11 => Some(Code::OutOfRange),
+            12 => Some(Code::Unimplemented),
+            13 => Some(Code::Internal),
+            14 => Some(Code::Unavailable),
+            15 => Some(Code::DataLoss),
+            16 => Some(Code::Unauthenticated),
+            _ => None,
+        }
+    }
+
+    pub fn grpc(&self) -> i32 {
+        match self {
+            Code::Cancelled => 1,
+            Code::Unknown => 2,
+            Code::InvalidArgument => 3,
+            Code::DeadlineExceeded => 4,
+            Code::NotFound => 5,
+            Code::AlreadyExists => 6,
+            Code::PermissionDenied => 7,
+            Code::ResourceExhausted => 8,
+            Code::FailedPrecondition => 9,
+            Code::Aborted => 10,
+            Code::OutOfRange => 11,
+            Code::Unimplemented => 12,
+            Code::Internal => 13,
+            Code::Unavailable => 14,
+            Code::DataLoss => 15,
+            Code::Unauthenticated => 16,
+        }
+    }
+
+    /// Returns the snake case name of the code, as reported in `rpc.code` attributes.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Code::Cancelled => "cancelled",
+            Code::Unknown => "unknown",
+            Code::InvalidArgument => "invalid_argument",
+            Code::DeadlineExceeded => "deadline_exceeded",
+            Code::NotFound => "not_found",
+            Code::AlreadyExists => "already_exists",
+            Code::PermissionDenied => "permission_denied",
+            Code::ResourceExhausted => "resource_exhausted",
+            Code::FailedPrecondition => "failed_precondition",
+            Code::Aborted => "aborted",
+            Code::OutOfRange => "out_of_range",
+            Code::Unimplemented => "unimplemented",
+            Code::Internal => "internal",
+            Code::Unavailable => "unavailable",
+            Code::DataLoss => "data_loss",
+            Code::Unauthenticated => "unauthenticated",
+        }
+    }
+
+    /// Returns the HTTP status matching the code, following the gRPC HTTP mapping.
+    pub fn http_status(&self) -> StatusCode {
+        match self {
+            Code::Cancelled => StatusCode::from_u16(499).expect("valid status code"),
+            Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+            Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => StatusCode::BAD_REQUEST,
+            Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            Code::NotFound => StatusCode::NOT_FOUND,
+            Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+            Code::PermissionDenied => StatusCode::FORBIDDEN,
+            Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+            Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+            Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Application error, carrying a code mapped to gRPC and HTTP statuses, a message safe to return
+/// to clients, metadata and the underlying error, if any.
+#[derive(Debug, Error)]
+#[error("{code}: {message}")]
+pub struct AppError {
+    code: Code,
+    message: String,
+    metadata: BTreeMap<String, String>,
+    #[source]
+    source: Option<AnyError>,
+}
+
+impl AppError {
+    pub fn new<M: Into<String>>(code: Code, message: M) -> Self {
+        AppError {
+            code,
+            message: message.into(),
+            metadata: BTreeMap::new(),
+            source: None,
+        }
+    }
+
+    pub fn not_found<M: Into<String>>(message: M) -> Self {
+        AppError::new(Code::NotFound, message)
+    }
+
+    pub fn invalid_argument<M: Into<String>>(message: M) -> Self {
+        AppError::new(Code::InvalidArgument, message)
+    }
+
+    pub fn unavailable<M: Into<String>>(message: M) -> Self {
+        AppError::new(Code::Unavailable, message)
+    }
+
+    pub fn internal<M: Into<String>>(message: M) -> Self {
+        AppError::new(Code::Internal, message)
+    }
+
+    pub fn with_metadata<K: Into<String>, V: ToString>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.to_string());
+        self
+    }
+
+    pub fn with_source<E: Into<AnyError>>(mut self, source: E) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    pub fn code(&self) -> Code {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    pub fn grpc_code(&self) -> i32 {
+        self.code.grpc()
+    }
+
+    pub fn http_status(&self) -> StatusCode {
+        self.code.http_status()
+    }
+}
+
+/// Wraps an unexpected error as an internal one, its details being kept out of the message.
+impl From<AnyError> for AppError {
+    fn from(err: AnyError) -> Self {
+        AppError::internal("internal error").with_source(err)
+    }
+}
+
+impl From<AppError> for StatusCode {
+    fn from(err: AppError) -> Self {
+        err.http_status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_grpc_codes() {
+        for code in 1..=16 {
+            assert_eq!(Code::from_grpc(code).map(|v| v.grpc()), Some(code));
+        }
+
+        assert_eq!(Code::from_grpc(0), None);
+        assert_eq!(Code::from_grpc(17), None);
+        assert_eq!(Code::from_grpc(-1), None);
+    }
+
+    #[test]
+    fn maps_codes_to_http_statuses() {
+        let statuses = [
+            (Code::Cancelled, 499),
+            (Code::Unknown, 500),
+            (Code::InvalidArgument, 400),
+            (Code::DeadlineExceeded, 504),
+            (Code::NotFound, 404),
+            (Code::AlreadyExists, 409),
+            (Code::PermissionDenied, 403),
+            (Code::ResourceExhausted, 429),
+            (Code::FailedPrecondition, 400),
+            (Code::Aborted, 409),
+            (Code::OutOfRange, 400),
+            (Code::Unimplemented, 501),
+            (Code::Internal, 500),
+            (Code::Unavailable, 503),
+            (Code::DataLoss, 500),
+            (Code::Unauthenticated, 401),
+        ];
+
+        for (code, status) in statuses {
+            assert_eq!(code.http_status().as_u16(), status, "{}", code);
+        }
+    }
+
+    #[test]
+    fn hides_wrapped_error_messages() {
+        let err = AppError::from(anyhow!("connection to 10.0.0.1 refused"));
+
+        assert_eq!(err.code(), Code::Internal);
+        assert_eq!(err.message(), "internal error");
+        assert_eq!(err.to_string(), "internal: internal error");
+        assert_eq!(
+            err.source().map(|v| v.to_string()).as_deref(),
+            Some("connection to 10.0.0.1 refused")
+        );
+        assert_eq!(StatusCode::from(err), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -1,3 +1,7 @@
+pub use app::{AppError, Code};
+
+mod app;
+
 pub mod prelude {
     pub use anyhow::{anyhow, bail, ensure, Context as _, Result};
     pub use thiserror::{self, Error};
@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+const PREFIX: &str = "usr";
+const LENGTH: usize = 24;
+const COUNT: usize = 1_000;
+
+fn single(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single");
+
+    group.bench_function("new", |b| {
+        b.iter(|| id::new(black_box(PREFIX), black_box(LENGTH)))
+    });
+
+    group.bench_function("new_into", |b| {
+        let mut buffer = String::new();
+        b.iter(|| id::new_into(&mut buffer, black_box(PREFIX), black_box(LENGTH)))
+    });
+
+    group.bench_function("inline", |b| {
+        b.iter(|| id::InlineId::<32>::new(black_box(PREFIX), black_box(LENGTH)))
+    });
+
+    group.finish();
+}
+
+fn bulk(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk");
+
+    group.bench_function("new", |b| {
+        b.iter(|| (0..COUNT).map(|_| id::new(PREFIX, LENGTH)).collect::<Vec<_>>())
+    });
+
+    group.bench_function("batch", |b| b.iter(|| id::batch(PREFIX, LENGTH, COUNT)));
+
+    group.bench_function("inline", |b| {
+        b.iter_batched(
+            || Vec::with_capacity(COUNT),
+            |mut ids| {
+                ids.extend((0..COUNT).map(|_| id::InlineId::<32>::new(PREFIX, LENGTH)));
+                ids
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, single, bulk);
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+use std::{fmt, ops::Deref, str};
+
+use rand::{distributions::Alphanumeric, Rng};
+
+/// Writes an id in the same format as `id::new` into a buffer, replacing its content and reusing
+/// its allocation.
+pub fn new_into(buffer: &mut String, prefix: &str, length: usize) {
+    buffer.clear();
+    buffer.reserve(prefix.len() + 1 + length);
+    buffer.push_str(prefix);
+    buffer.push('_');
+    buffer.extend(
+        rand::thread_rng()
+            .sample_iter(Alphanumeric)
+            .take(length)
+            .map(char::from),
+    );
+}
+
+/// Generates `count` ids in the same format as `id::new`.
+pub fn batch(prefix: &str, length: usize, count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+
+    (0..count)
+        .map(|_| {
+            let mut id = String::with_capacity(prefix.len() + 1 + length);
+            id.push_str(prefix);
+            id.push('_');
+            id.extend((&mut rng).sample_iter(Alphanumeric).take(length).map(char::from));
+            id
+        })
+        .collect()
+}
+
+/// Id in the same format as `id::new`, stored inline in a buffer of `N` bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InlineId<const N: usize> {
+    bytes: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> InlineId<N> {
+    /// Generates an id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix, separator and random part do not fit in `N` bytes.
+    pub fn new(prefix: &str, length: usize) -> Self {
+        let total = prefix.len() + 1 + length;
+        assert!(total <= N, "id of {} bytes does not fit in {} bytes", total, N);
+
+        let mut bytes = [0; N];
+        bytes[..prefix.len()].copy_from_slice(prefix.as_bytes());
+        bytes[prefix.len()] = b'_';
+
+        let mut rng = rand::thread_rng();
+        for byte in &mut bytes[prefix.len() + 1..total] {
+            *byte = rng.sample(Alphanumeric);
+        }
+
+        InlineId { bytes, length: total }
+    }
+
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(&self.bytes[..self.length]).expect("ids are valid UTF-8")
+    }
+}
+
+impl<const N: usize> Deref for InlineId<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> fmt::Display for InlineId<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for InlineId<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
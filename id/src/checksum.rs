@@ -0,0 +1,127 @@
+use crate::{generator::ALPHANUMERIC, ParseError};
+
+/// Generates an id whose last character is a check character computed over the random part (Luhn
+/// mod N), catching single character typos and most adjacent transpositions.
+///
+/// Panics when `length` is zero, the check character needing a random part to cover.
+pub fn new_checked(prefix: &str, length: usize) -> String {
+    assert!(length > 0, "checksummed ids must have a random part");
+
+    append_checksum(&crate::new(prefix, length)).expect("generated ids are valid")
+}
+
+/// Appends a check character to a prefixed alphanumeric id, such as one returned by `id::new` or
+/// `id::sortable`, failing on ids `validate` would reject once checksummed.
+pub fn append_checksum(id: &str) -> Result<String, ParseError> {
+    let (_, value) = id
+        .rsplit_once('_')
+        .ok_or_else(|| ParseError::Separator(id.to_string()))?;
+
+    check_characters(id, value)?;
+
+    if value.is_empty() {
+        return Err(ParseError::Length {
+            expected: 1,
+            found: 0,
+        });
+    }
+
+    let sum = luhn(value, 2).expect("characters are alphanumeric");
+
+    Ok(format!("{}{}", id, char::from(ALPHANUMERIC[(62 - sum) % 62])))
+}
+
+/// Validates the check character of an id generated by `new_checked` or `append_checksum`.
+pub fn validate(id: &str) -> Result<(), ParseError> {
+    let (_, value) = id
+        .rsplit_once('_')
+        .ok_or_else(|| ParseError::Separator(id.to_string()))?;
+
+    check_characters(id, value)?;
+
+    match luhn(value, 1) {
+        Some(0) if value.len() > 1 => Ok(()),
+        _ => Err(ParseError::Checksum(id.to_string())),
+    }
+}
+
+/// Checks the random part of an id (ending it) is alphanumeric.
+fn check_characters(id: &str, value: &str) -> Result<(), ParseError> {
+    match value
+        .chars()
+        .enumerate()
+        .find(|(_, v)| !v.is_ascii_alphanumeric())
+    {
+        Some((position, found)) => Err(ParseError::Character {
+            found,
+            position: id.len() - value.len() + position,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Computes the Luhn mod N sum of a value, starting from its last character with the given factor.
+fn luhn(value: &str, mut factor: usize) -> Option<usize> {
+    value.bytes().rev().try_fold(0, |sum, c| {
+        let addend = factor * ALPHANUMERIC.iter().position(|v| *v == c)?;
+        factor = 3 - factor;
+
+        Some((sum + addend / 62 + addend % 62) % 62)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_appended_checksums() {
+        let id = append_checksum("usr_3kTMd9fXa").unwrap();
+
+        assert_eq!(validate(&id), Ok(()));
+        assert_eq!(validate(&new_checked("usr", 24)), Ok(()));
+
+        for position in 4..id.len() {
+            let mut typo = id.clone().into_bytes();
+            typo[position] = if typo[position] == b'x' { b'y' } else { b'x' };
+
+            assert!(validate(&String::from_utf8(typo).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "checksummed ids must have a random part")]
+    fn rejects_empty_random_parts() {
+        new_checked("usr", 0);
+    }
+
+    #[test]
+    fn rejects_unprefixed_ids() {
+        assert_eq!(
+            append_checksum("abc123"),
+            Err(ParseError::Separator("abc123".to_string()))
+        );
+        assert_eq!(
+            validate("abc1234"),
+            Err(ParseError::Separator("abc1234".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_non_alphanumeric_ids() {
+        assert_eq!(
+            append_checksum("usr_3kT-d9f"),
+            Err(ParseError::Character {
+                found: '-',
+                position: 7
+            })
+        );
+        assert_eq!(
+            append_checksum("usr_"),
+            Err(ParseError::Length {
+                expected: 1,
+                found: 0
+            })
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use sha2::{Digest, Sha256};
+
+use crate::generator::ALPHANUMERIC;
+
+/// Largest multiple of the alphabet size fitting in a byte, larger bytes being rejected so that
+/// every character is equally likely.
+const REJECTION_THRESHOLD: u8 = 62 * 4;
+
+/// Derives an id from a namespace and a name (in the spirit of UUIDv5), in the same format as
+/// `id::new`: the same inputs always give the same id, e.g. to keep imports idempotent.
+pub fn derive(prefix: &str, namespace: &str, name: &str, length: usize) -> String {
+    let mut id = String::with_capacity(prefix.len() + 1 + length);
+    id.push_str(prefix);
+    id.push('_');
+
+    let mut counter = 0u64;
+
+    while id.len() < prefix.len() + 1 + length {
+        let block = Sha256::new()
+            .chain_update((namespace.len() as u64).to_be_bytes())
+            .chain_update(namespace)
+            .chain_update(name)
+            .chain_update(counter.to_be_bytes())
+            .finalize();
+
+        id.extend(
+            block
+                .iter()
+                .filter(|v| **v < REJECTION_THRESHOLD)
+                .map(|v| char::from(ALPHANUMERIC[usize::from(*v % 62)]))
+                .take(prefix.len() + 1 + length - id.len()),
+        );
+
+        counter += 1;
+    }
+
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_stable_ids() {
+        assert_eq!(derive("p", "a", "bc", 10), "p_htCoFiYar6");
+        assert_eq!(
+            derive("usr", "imports", "alice@example.com", 24),
+            "usr_k1t1QfqJExHl4cMRCj3jCGPB"
+        );
+        // Longer ids span several hash blocks, shorter ones being their prefix.
+        assert_eq!(
+            derive("p", "a", "bc", 60),
+            "p_htCoFiYar6uCpQz2AxgbWqBfCTt0HLAHK6qSXAHO0lxGRrxiLAoS87ui5QtU"
+        );
+    }
+
+    #[test]
+    fn separates_namespaces_from_names() {
+        assert_eq!(derive("p", "ab", "c", 10), "p_OP13lsdCXo");
+        assert_ne!(derive("p", "a", "bc", 10), derive("p", "ab", "c", 10));
+    }
+}
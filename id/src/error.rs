@@ -0,0 +1,23 @@
+use errors::prelude::*;
+
+/// Error returned when parsing a malformed id.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("invalid id prefix {found:?}, expected {expected:?}")]
+    Prefix { expected: String, found: String },
+
+    #[error("missing `_` separator in id {0:?}")]
+    Separator(String),
+
+    #[error("invalid id length {found}, expected {expected}")]
+    Length { expected: usize, found: usize },
+
+    #[error("invalid checksum in id {0:?}")]
+    Checksum(String),
+
+    #[error("id {0:?} is out of range")]
+    Overflow(String),
+
+    #[error("invalid character {found:?} at position {position} in id")]
+    Character { found: char, position: usize },
+}
@@ -0,0 +1,126 @@
+use std::sync::Mutex;
+
+use rand::{
+    rngs::{OsRng, StdRng},
+    Rng, RngCore, SeedableRng,
+};
+
+use crate::{sortable::CROCKFORD, DEFAULT_LENGTH};
+
+/// Alphanumeric alphabet, in ASCII order so that base62-rendered values (e.g. snowflake ids) sort
+/// numerically.
+pub(crate) const ALPHANUMERIC: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// Characters random id parts are drawn from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alphabet {
+    /// `0-9A-Za-z`, as generated by `id::new`.
+    Alphanumeric,
+    /// Lowercase Crockford base32, without ambiguous characters (`i`, `l`, `o`, `u`).
+    Crockford,
+    /// Bitcoin base58, without `0`, `O`, `I` and `l`.
+    Base58,
+    /// Lowercase hexadecimal.
+    Hex,
+    /// URL-safe characters (`A-Za-z0-9-~`), `~` replacing the base64 `_` so that ids can still be
+    /// split on their last separator.
+    UrlSafe,
+}
+
+impl Alphabet {
+    pub fn chars(&self) -> &'static [u8] {
+        match self {
+            Alphabet::Alphanumeric => ALPHANUMERIC,
+            Alphabet::Crockford => CROCKFORD,
+            Alphabet::Base58 => b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            Alphabet::Hex => b"0123456789abcdef",
+            Alphabet::UrlSafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-~",
+        }
+    }
+}
+
+/// Prefixed id generator with a configurable random number generator and alphabet.
+///
+/// The OS random number generator is used by default, a seeded one allowing deterministic ids in
+/// tests.
+pub struct IdGenerator<R = OsRng> {
+    prefix: String,
+    length: usize,
+    alphabet: Alphabet,
+    rng: Mutex<R>,
+}
+
+impl IdGenerator {
+    pub fn new(prefix: &str) -> Self {
+        IdGenerator {
+            prefix: prefix.to_string(),
+            length: DEFAULT_LENGTH,
+            alphabet: Alphabet::Alphanumeric,
+            rng: Mutex::new(OsRng),
+        }
+    }
+}
+
+impl<R: RngCore> IdGenerator<R> {
+    /// Sets the length of the random part (defaults to `DEFAULT_LENGTH`).
+    pub fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    pub fn with_alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    pub fn with_rng<S: RngCore>(self, rng: S) -> IdGenerator<S> {
+        IdGenerator {
+            prefix: self.prefix,
+            length: self.length,
+            alphabet: self.alphabet,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Uses a deterministic random number generator, mostly useful in tests.
+    pub fn with_seed(self, seed: u64) -> IdGenerator<StdRng> {
+        self.with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    pub fn generate(&self) -> String {
+        let chars = self.alphabet.chars();
+        let mut rng = self.rng.lock().unwrap_or_else(|v| v.into_inner());
+
+        let value: String = (0..self.length)
+            .map(|_| char::from(chars[rng.gen_range(0..chars.len())]))
+            .collect();
+
+        format!("{}_{}", self.prefix, value)
+    }
+
+    /// Returns the number of random bits of generated ids.
+    pub fn entropy_bits(&self) -> f64 {
+        self.length as f64 * (self.alphabet.chars().len() as f64).log2()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_generates_separators() {
+        for alphabet in [
+            Alphabet::Alphanumeric,
+            Alphabet::Crockford,
+            Alphabet::Base58,
+            Alphabet::Hex,
+            Alphabet::UrlSafe,
+        ] {
+            assert!(!alphabet.chars().contains(&b'_'), "{:?}", alphabet);
+
+            let id = IdGenerator::new("usr").with_alphabet(alphabet).generate();
+            assert_eq!(id.rsplit_once('_').map(|(prefix, _)| prefix), Some("usr"));
+        }
+    }
+}
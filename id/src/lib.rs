@@ -1,5 +1,23 @@
 use rand::{distributions::Alphanumeric, Rng};
 
+pub use batch::{batch, new_into, InlineId};
+pub use checksum::{append_checksum, new_checked, validate};
+pub use derive::derive;
+pub use error::ParseError;
+pub use generator::{Alphabet, IdGenerator};
+pub use snowflake::{Snowflake, SnowflakeError, SnowflakeLayout, SnowflakeParts};
+pub use sortable::{sortable, timestamp};
+pub use typed::{check, DEFAULT_LENGTH};
+
+mod batch;
+mod checksum;
+mod derive;
+mod error;
+mod generator;
+mod snowflake;
+mod sortable;
+mod typed;
+
 pub fn new(prefix: &str, length: usize) -> String {
     format!(
         "{}_{}",
@@ -11,3 +29,7 @@ pub fn new(prefix: &str, length: usize) -> String {
             .collect::<String>()
     )
 }
+
+pub mod __internal {
+    pub use serde;
+}
@@ -0,0 +1,333 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use errors::prelude::*;
+
+use crate::{generator::ALPHANUMERIC, typed::check, ParseError};
+
+/// Number of base62 characters needed to render any 64-bit value.
+const RENDERED_LENGTH: usize = 11;
+
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum SnowflakeError {
+    #[error("invalid snowflake layout: {0}")]
+    Layout(String),
+
+    #[error("node id {node} does not fit in {bits} bits")]
+    Node { node: u64, bits: u8 },
+
+    #[error("clock is {0:?} behind the last generated id")]
+    ClockRegression(Duration),
+
+    #[error("snowflake timestamp overflow")]
+    Overflow,
+}
+
+/// Bit layout of snowflake ids: timestamp (milliseconds since the epoch), node id and sequence,
+/// most significant first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnowflakeLayout {
+    timestamp_bits: u8,
+    node_bits: u8,
+    sequence_bits: u8,
+}
+
+impl SnowflakeLayout {
+    pub fn new(timestamp_bits: u8, node_bits: u8, sequence_bits: u8) -> Result<Self, SnowflakeError> {
+        if timestamp_bits == 0 || sequence_bits == 0 {
+            return Err(SnowflakeError::Layout(
+                "timestamp and sequence must have at least one bit".to_string(),
+            ));
+        }
+
+        if u32::from(timestamp_bits) + u32::from(node_bits) + u32::from(sequence_bits) > 63 {
+            return Err(SnowflakeError::Layout("ids must fit in 63 bits".to_string()));
+        }
+
+        Ok(SnowflakeLayout {
+            timestamp_bits,
+            node_bits,
+            sequence_bits,
+        })
+    }
+}
+
+/// Twitter layout: 41 bits of timestamp, 10 bits of node id and 12 bits of sequence.
+impl Default for SnowflakeLayout {
+    fn default() -> Self {
+        SnowflakeLayout {
+            timestamp_bits: 41,
+            node_bits: 10,
+            sequence_bits: 12,
+        }
+    }
+}
+
+/// Decoded snowflake id.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnowflakeParts {
+    pub timestamp: SystemTime,
+    pub node: u64,
+    pub sequence: u64,
+}
+
+/// Snowflake-style 64-bit id generator, safe to share across threads without locking.
+///
+/// When the clock moves backwards, ids keep being generated from the last timestamp (borrowing
+/// from the following milliseconds once the sequence is exhausted) until the clock has fallen
+/// behind by more than the maximum drift.
+pub struct Snowflake {
+    layout: SnowflakeLayout,
+    epoch: SystemTime,
+    node: u64,
+    max_drift: Duration,
+    /// Last generated timestamp and sequence.
+    state: AtomicU64,
+}
+
+impl Snowflake {
+    /// Default epoch, 2020-01-01T00:00:00Z.
+    pub const EPOCH: Duration = Duration::from_millis(1_577_836_800_000);
+
+    pub fn new(node: u64) -> Result<Self, SnowflakeError> {
+        Snowflake::with_layout(SnowflakeLayout::default(), node)
+    }
+
+    pub fn with_layout(layout: SnowflakeLayout, node: u64) -> Result<Self, SnowflakeError> {
+        if node >= 1 << layout.node_bits {
+            return Err(SnowflakeError::Node {
+                node,
+                bits: layout.node_bits,
+            });
+        }
+
+        Ok(Snowflake {
+            layout,
+            epoch: UNIX_EPOCH + Snowflake::EPOCH,
+            node,
+            max_drift: Duration::from_secs(1),
+            state: AtomicU64::new(0),
+        })
+    }
+
+    pub fn with_epoch(mut self, epoch: SystemTime) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// Sets how far the clock may fall behind the last generated id before generation fails
+    /// (defaults to one second).
+    pub fn with_max_drift(mut self, max_drift: Duration) -> Self {
+        self.max_drift = max_drift;
+        self
+    }
+
+    pub fn generate(&self) -> Result<u64, SnowflakeError> {
+        let SnowflakeLayout {
+            timestamp_bits,
+            node_bits,
+            sequence_bits,
+        } = self.layout;
+
+        let sequence_mask = (1 << sequence_bits) - 1;
+        let max_drift = self.max_drift.as_millis() as u64;
+
+        loop {
+            let now = SystemTime::now()
+                .duration_since(self.epoch)
+                .unwrap_or_default()
+                .as_millis() as u64;
+
+            let last = self.state.load(Ordering::Acquire);
+            let (timestamp, sequence) = (last >> sequence_bits, last & sequence_mask);
+
+            let (timestamp, sequence) = match (timestamp, sequence) {
+                (timestamp, _) if now > timestamp => (now, 0),
+                (timestamp, sequence) if sequence < sequence_mask => (timestamp, sequence + 1),
+                (timestamp, _) => (timestamp + 1, 0),
+            };
+
+            if timestamp > now + max_drift {
+                return Err(SnowflakeError::ClockRegression(Duration::from_millis(
+                    timestamp - now,
+                )));
+            }
+
+            if timestamp >= 1 << timestamp_bits {
+                return Err(SnowflakeError::Overflow);
+            }
+
+            let next = (timestamp << sequence_bits) | sequence;
+
+            if self
+                .state
+                .compare_exchange_weak(last, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return Ok((timestamp << (node_bits + sequence_bits))
+                    | (self.node << sequence_bits)
+                    | sequence);
+            }
+        }
+    }
+
+    pub fn decode(&self, id: u64) -> SnowflakeParts {
+        let SnowflakeLayout {
+            node_bits,
+            sequence_bits,
+            ..
+        } = self.layout;
+
+        SnowflakeParts {
+            timestamp: self.epoch + Duration::from_millis(id >> (node_bits + sequence_bits)),
+            node: (id >> sequence_bits) & ((1 << node_bits) - 1),
+            sequence: id & ((1 << sequence_bits) - 1),
+        }
+    }
+
+    /// Renders an id with the `prefix_` format, as fixed-length base62 so that rendered ids sort in
+    /// the same order as the numeric ones.
+    pub fn render(prefix: &str, id: u64) -> String {
+        let value: String = (0..RENDERED_LENGTH)
+            .rev()
+            .map(|i| char::from(ALPHANUMERIC[((id / 62u64.pow(i as u32)) % 62) as usize]))
+            .collect();
+
+        format!("{}_{}", prefix, value)
+    }
+
+    /// Parses an id rendered with `render`.
+    pub fn parse(id: &str, prefix: &str) -> Result<u64, ParseError> {
+        check(id, prefix, RENDERED_LENGTH)?;
+
+        let value = &id[id.len() - RENDERED_LENGTH..];
+
+        value
+            .bytes()
+            .try_fold(0u64, |acc, c| {
+                let digit = ALPHANUMERIC.iter().position(|v| *v == c)? as u64;
+                acc.checked_mul(62)?.checked_add(digit)
+            })
+            .ok_or_else(|| ParseError::Overflow(id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::Arc, thread};
+
+    use super::*;
+
+    #[test]
+    fn generates_unique_increasing_ids_across_threads() {
+        let snowflake = Arc::new(Snowflake::new(1).unwrap());
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let snowflake = snowflake.clone();
+                thread::spawn(move || {
+                    (0..20_000)
+                        .map(|_| snowflake.generate().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+
+        for handle in handles {
+            let ids = handle.join().unwrap();
+
+            assert!(ids.windows(2).all(|v| v[0] < v[1]));
+            assert!(ids.into_iter().all(|id| seen.insert(id)));
+        }
+
+        assert_eq!(seen.len(), 80_000);
+    }
+
+    #[test]
+    fn borrows_following_milliseconds_until_max_drift() {
+        // The clock always reads 0 with an epoch in the future.
+        let snowflake = Snowflake::with_layout(SnowflakeLayout::new(41, 0, 1).unwrap(), 0)
+            .unwrap()
+            .with_epoch(SystemTime::now() + Duration::from_secs(3_600))
+            .with_max_drift(Duration::from_millis(5));
+
+        let ids: Vec<u64> = (0..11).map(|_| snowflake.generate().unwrap()).collect();
+
+        assert_eq!(ids, (1..12).collect::<Vec<_>>());
+        assert_eq!(
+            snowflake.generate(),
+            Err(SnowflakeError::ClockRegression(Duration::from_millis(6)))
+        );
+    }
+
+    #[test]
+    fn rejects_timestamp_overflow() {
+        let snowflake = Snowflake::with_layout(SnowflakeLayout::new(8, 0, 1).unwrap(), 0)
+            .unwrap()
+            .with_epoch(UNIX_EPOCH);
+
+        assert_eq!(snowflake.generate(), Err(SnowflakeError::Overflow));
+    }
+
+    #[test]
+    fn validates_layouts_and_nodes() {
+        assert!(SnowflakeLayout::new(41, 10, 12).is_ok());
+        assert!(SnowflakeLayout::new(41, 10, 13).is_err());
+        assert!(SnowflakeLayout::new(0, 10, 12).is_err());
+        assert!(SnowflakeLayout::new(41, 10, 0).is_err());
+        assert!(SnowflakeLayout::new(255, 255, 255).is_err());
+
+        assert!(Snowflake::new(1023).is_ok());
+        assert_eq!(
+            Snowflake::new(1024).err(),
+            Some(SnowflakeError::Node { node: 1024, bits: 10 })
+        );
+    }
+
+    #[test]
+    fn decodes_ids() {
+        let snowflake = Snowflake::new(5).unwrap();
+
+        assert_eq!(
+            snowflake.decode((1_000 << 22) | (5 << 12) | 7),
+            SnowflakeParts {
+                timestamp: UNIX_EPOCH + Snowflake::EPOCH + Duration::from_secs(1),
+                node: 5,
+                sequence: 7,
+            }
+        );
+
+        let id = snowflake.generate().unwrap();
+        assert_eq!(snowflake.decode(id).node, 5);
+    }
+
+    #[test]
+    fn renders_ids_in_numeric_order() {
+        let ids = [0, 1, 61, 62, 63, 3_843, 1 << 40, u64::MAX - 1, u64::MAX];
+
+        let rendered: Vec<String> = ids.iter().map(|id| Snowflake::render("x", *id)).collect();
+
+        assert!(rendered.windows(2).all(|v| v[0] < v[1]));
+        assert_eq!(rendered[0], "x_00000000000");
+        assert_eq!(rendered[ids.len() - 1], "x_LygHa16AHYF");
+
+        for (id, rendered) in ids.iter().zip(&rendered) {
+            assert_eq!(Snowflake::parse(rendered, "x"), Ok(*id));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        assert_eq!(
+            Snowflake::parse("x_zzzzzzzzzzz", "x"),
+            Err(ParseError::Overflow("x_zzzzzzzzzzz".to_string()))
+        );
+        assert!(Snowflake::parse("x_0000000000", "x").is_err());
+        assert!(Snowflake::parse("y_00000000000", "x").is_err());
+        assert!(Snowflake::parse("x_0000000000-", "x").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+
+/// Crockford base32 alphabet (lowercased), whose ASCII order matches the numeric order.
+pub(crate) const CROCKFORD: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+const TIMESTAMP_LENGTH: usize = 10;
+const RANDOM_LENGTH: usize = 16;
+const RANDOM_BITS: u32 = 80;
+
+/// Last generated value, so that ids generated within the same millisecond keep increasing.
+static LAST: Mutex<(u64, u128)> = Mutex::new((0, 0));
+
+/// Generates a time-ordered id (`prefix_` followed by a ULID: a 48-bit millisecond timestamp and
+/// 80 random bits, in Crockford base32), ids sorting lexicographically in creation order.
+pub fn sortable(prefix: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+
+    let (millis, random) = {
+        let mut last = LAST.lock().unwrap_or_else(|v| v.into_inner());
+
+        *last = match *last {
+            (millis, bits) if now <= millis && bits + 1 < 1 << RANDOM_BITS => (millis, bits + 1),
+            (millis, _) if now <= millis => (millis + 1, random()),
+            _ => (now, random()),
+        };
+
+        *last
+    };
+
+    let value = (u128::from(millis) << RANDOM_BITS) | random;
+
+    format!("{}_{}", prefix, encode(value, TIMESTAMP_LENGTH + RANDOM_LENGTH))
+}
+
+/// Extracts the creation time embedded in an id generated by `sortable`.
+pub fn timestamp(id: &str) -> Option<SystemTime> {
+    let (_, value) = id.rsplit_once('_')?;
+
+    if value.len() != TIMESTAMP_LENGTH + RANDOM_LENGTH || !value.is_ascii() {
+        return None;
+    }
+
+    let millis = decode(value.get(..TIMESTAMP_LENGTH)?)?;
+
+    UNIX_EPOCH.checked_add(Duration::from_millis(u64::try_from(millis).ok()?))
+}
+
+fn random() -> u128 {
+    rand::thread_rng().gen::<u128>() >> (128 - RANDOM_BITS)
+}
+
+/// Encodes the lowest `length * 5` bits of a value, most significant first.
+pub(crate) fn encode(value: u128, length: usize) -> String {
+    (0..length)
+        .rev()
+        .map(|i| char::from(CROCKFORD[((value >> (i * 5)) & 0x1f) as usize]))
+        .collect()
+}
+
+/// Decodes a Crockford base32 value, case-insensitively and accepting the usual substitutions
+/// (`i`/`l` for `1`, `o` for `0`).
+pub(crate) fn decode(value: &str) -> Option<u128> {
+    value.chars().try_fold(0u128, |acc, c| {
+        let c = match c.to_ascii_lowercase() {
+            'i' | 'l' => '1',
+            'o' => '0',
+            c => c,
+        };
+
+        let digit = CROCKFORD.iter().position(|v| char::from(*v) == c)?;
+
+        acc.checked_mul(32)?.checked_add(digit as u128)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_values() {
+        for value in [0, 1, 31, 32, u128::from(u64::MAX), (1 << 125) - 1] {
+            assert_eq!(decode(&encode(value, 26)), Some(value));
+        }
+
+        assert_eq!(encode(0, 4), "0000");
+        assert_eq!(decode("ILO"), decode("110"));
+        assert_eq!(decode("u"), None);
+    }
+
+    #[test]
+    fn embeds_creation_time() {
+        let before = SystemTime::now() - Duration::from_millis(1);
+        let id = sortable("usr");
+        let after = SystemTime::now() + Duration::from_millis(1);
+
+        let created = timestamp(&id).unwrap();
+        assert!(before <= created && created <= after, "{:?}", created);
+    }
+
+    #[test]
+    fn sorts_in_creation_order() {
+        let ids: Vec<String> = (0..10_000).map(|_| sortable("usr")).collect();
+
+        assert!(ids.windows(2).all(|v| v[0] < v[1]));
+    }
+
+    #[test]
+    fn rejects_malformed_ids() {
+        for id in [
+            "",
+            "usr",
+            "usr_",
+            "usr_01h",
+            "usr_01hxxxxxxxxxxxxxxxxxxxxxxxx",
+            "usr_uuuuuuuuuuuuuuuuuuuuuuuuuu",
+            "usr_aééééééééééééa",
+            "usr_ééééééééééééé",
+        ] {
+            assert_eq!(timestamp(id), None, "{:?}", id);
+        }
+    }
+}
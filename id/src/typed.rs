@@ -0,0 +1,164 @@
+use crate::ParseError;
+
+/// Length of the random part of ids defined with `define_id!` when none is given.
+pub const DEFAULT_LENGTH: usize = 24;
+
+/// Checks an id has the given prefix, followed by the `_` separator and `length` alphanumeric
+/// characters, as generated by `id::new`.
+pub fn check(id: &str, prefix: &str, length: usize) -> Result<(), ParseError> {
+    let (found, value) = id
+        .rsplit_once('_')
+        .ok_or_else(|| ParseError::Separator(id.to_string()))?;
+
+    if found != prefix {
+        return Err(ParseError::Prefix {
+            expected: prefix.to_string(),
+            found: found.to_string(),
+        });
+    }
+
+    if let Some((position, found)) = value
+        .chars()
+        .enumerate()
+        .find(|(_, v)| !v.is_ascii_alphanumeric())
+    {
+        return Err(ParseError::Character {
+            found,
+            position: prefix.len() + 1 + position,
+        });
+    }
+
+    if value.len() != length {
+        return Err(ParseError::Length {
+            expected: length,
+            found: value.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Defines a prefixed id newtype, e.g. `define_id!(pub UserId, "usr")` for `usr_`-prefixed ids,
+/// with an optional random part length (defaulting to `DEFAULT_LENGTH`), possibly followed by
+/// `checksum` to append a check character to generated ids and validate it when parsing.
+#[macro_export]
+macro_rules! define_id {
+    ($(#[$meta:meta])* $vis:vis $name:ident, $prefix:expr) => {
+        $crate::define_id!(@define $(#[$meta])* $vis $name, $prefix, $crate::DEFAULT_LENGTH, false);
+    };
+
+    ($(#[$meta:meta])* $vis:vis $name:ident, $prefix:expr, $length:expr) => {
+        $crate::define_id!(@define $(#[$meta])* $vis $name, $prefix, $length, false);
+    };
+
+    ($(#[$meta:meta])* $vis:vis $name:ident, $prefix:expr, $length:expr, checksum) => {
+        $crate::define_id!(@define $(#[$meta])* $vis $name, $prefix, $length, true);
+    };
+
+    (@define $(#[$meta:meta])* $vis:vis $name:ident, $prefix:expr, $length:expr, $checksum:expr) => {
+        const _: () = ::core::assert!(!$checksum || $length > 0, "checksummed ids must have a random part");
+
+        $(#[$meta])*
+        #[derive(
+            ::core::clone::Clone,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+            ::core::cmp::PartialOrd,
+            ::core::cmp::Ord,
+        )]
+        $vis struct $name(::std::string::String);
+
+        impl $name {
+            pub const PREFIX: &'static str = $prefix;
+            pub const LENGTH: usize = $length;
+            pub const CHECKSUM: bool = $checksum;
+
+            pub fn new() -> Self {
+                if Self::CHECKSUM {
+                    $name($crate::new_checked(Self::PREFIX, Self::LENGTH))
+                } else {
+                    $name($crate::new(Self::PREFIX, Self::LENGTH))
+                }
+            }
+
+            /// Derives an id from a namespace and a name, the same inputs always giving the same id.
+            pub fn derive(namespace: &str, name: &str) -> Self {
+                let id = $crate::derive(Self::PREFIX, namespace, name, Self::LENGTH);
+
+                if Self::CHECKSUM {
+                    $name($crate::append_checksum(&id).expect("derived ids are valid"))
+                } else {
+                    $name(id)
+                }
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> ::std::string::String {
+                self.0
+            }
+        }
+
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl ::core::str::FromStr for $name {
+            type Err = $crate::ParseError;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                $crate::check(s, Self::PREFIX, Self::LENGTH + Self::CHECKSUM as usize)?;
+
+                if Self::CHECKSUM {
+                    $crate::validate(s)?;
+                }
+
+                ::core::result::Result::Ok($name(::std::borrow::ToOwned::to_owned(s)))
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::From<$name> for ::std::string::String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl $crate::__internal::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__internal::serde::Serializer,
+            {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> $crate::__internal::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::__internal::serde::Deserializer<'de>,
+            {
+                let value =
+                    <::std::string::String as $crate::__internal::serde::Deserialize>::deserialize(deserializer)?;
+                <Self as ::core::str::FromStr>::from_str(&value).map_err($crate::__internal::serde::de::Error::custom)
+            }
+        }
+    };
+}
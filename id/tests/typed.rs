@@ -0,0 +1,105 @@
+use id::ParseError;
+
+mod ids {
+    // Checks the expansion does not depend on the prelude.
+    #![no_implicit_prelude]
+
+    ::id::define_id!(pub UserId, "usr");
+    ::id::define_id!(pub ShortId, "sh", 6);
+    ::id::define_id!(pub OrderId, "ord", 10, checksum);
+}
+
+use ids::{OrderId, ShortId, UserId};
+
+#[test]
+fn generates_and_parses_ids() {
+    let id = UserId::new();
+
+    assert!(id.as_str().starts_with("usr_"));
+    assert_eq!(id.as_str().len(), 4 + id::DEFAULT_LENGTH);
+    assert_eq!(id.to_string().parse::<UserId>(), Ok(id.clone()));
+    assert_eq!(String::from(id.clone()), id.into_string());
+
+    let id = ShortId::new();
+    assert_eq!(id.as_str().len(), 9);
+    assert_eq!(id.as_str().parse::<ShortId>(), Ok(id));
+
+    assert_eq!(
+        UserId::derive("imports", "alice"),
+        UserId::derive("imports", "alice")
+    );
+    assert_ne!(
+        UserId::derive("imports", "alice"),
+        UserId::derive("imports", "bob")
+    );
+}
+
+#[test]
+fn rejects_malformed_ids() {
+    assert_eq!(
+        "usr".parse::<ShortId>(),
+        Err(ParseError::Separator("usr".to_string()))
+    );
+    assert_eq!(
+        "usr_abcdef".parse::<ShortId>(),
+        Err(ParseError::Prefix {
+            expected: "sh".to_string(),
+            found: "usr".to_string()
+        })
+    );
+    assert_eq!(
+        "sh_abcde".parse::<ShortId>(),
+        Err(ParseError::Length {
+            expected: 6,
+            found: 5
+        })
+    );
+    assert_eq!(
+        "sh_abc-ef".parse::<ShortId>(),
+        Err(ParseError::Character {
+            found: '-',
+            position: 6
+        })
+    );
+}
+
+#[test]
+fn validates_checksums() {
+    let id = OrderId::new();
+
+    assert_eq!(id.as_str().len(), 4 + 10 + 1);
+    assert_eq!(id.as_str().parse::<OrderId>(), Ok(id.clone()));
+    assert_eq!(
+        OrderId::derive("imports", "alice").as_str().parse::<OrderId>(),
+        Ok(OrderId::derive("imports", "alice"))
+    );
+
+    let last = id.as_str().chars().last().unwrap();
+    let other = if last == 'a' { 'b' } else { 'a' };
+    let corrupted = format!("{}{}", &id.as_str()[..id.as_str().len() - 1], other);
+    assert_eq!(
+        corrupted.parse::<OrderId>(),
+        Err(ParseError::Checksum(corrupted.clone()))
+    );
+
+    // The random part alone is too short once the check character is expected.
+    assert_eq!(
+        "ord_abcdefghij".parse::<OrderId>(),
+        Err(ParseError::Length {
+            expected: 11,
+            found: 10
+        })
+    );
+}
+
+#[test]
+fn round_trips_through_serde() {
+    let id = OrderId::new();
+
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{}\"", id));
+    assert_eq!(serde_json::from_str::<OrderId>(&json).unwrap(), id);
+
+    let err = serde_json::from_str::<OrderId>("\"usr_abcdefghijk\"").unwrap_err();
+    assert!(err.to_string().contains("invalid id prefix"), "{}", err);
+}
@@ -45,7 +45,7 @@ impl opentelemetry_sdk::export::logs::LogExporter for LogsExporter {
                     .into_iter()
                     .filter_map(|(key, value)| {
                         let mut key: String = key.into();
-                        key.push_str("=");
+                        key.push('=');
 
                         let value = match value {
                             AnyValue::Int(value) => value.to_string(),
@@ -72,7 +72,7 @@ impl opentelemetry_sdk::export::logs::LogExporter for LogsExporter {
                     body,
                 ));
 
-                if attributes.len() > 0 {
+                if !attributes.is_empty() {
                     let _ = writer.write_fmt(format_args!("{} {}", ",".dimmed(), attributes.join(" "),));
                 }
 
@@ -3,9 +3,12 @@ use std::ops::Deref;
 use tracing::{span, Subscriber};
 use tracing_subscriber::{field::Visit, registry::LookupSpan, Layer};
 
+#[cfg_attr(not(feature = "rpc"), allow(dead_code))]
 pub const RPC_METHOD: &str = "rpc.method";
+#[cfg_attr(not(feature = "rpc"), allow(dead_code))]
 pub const RPC_SERVICE: &str = "rpc.service";
 
+#[cfg_attr(not(feature = "rpc"), allow(dead_code))]
 #[derive(Debug)]
 pub struct RPCMethod(pub String);
 
@@ -17,6 +20,7 @@ impl Deref for RPCMethod {
     }
 }
 
+#[cfg_attr(not(feature = "rpc"), allow(dead_code))]
 #[derive(Debug)]
 pub struct RPCService(pub String);
 
@@ -68,6 +72,7 @@ where
     }
 }
 
+#[cfg_attr(not(feature = "rpc"), allow(dead_code))]
 #[derive(Default)]
 pub struct RPCVisitor {
     rpc_method: Option<String>,
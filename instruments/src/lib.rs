@@ -1,4 +1,4 @@
-use std::{env, result, str::FromStr};
+use std::{env, fmt, result, str::FromStr};
 
 use opentelemetry::KeyValue;
 use opentelemetry_sdk::Resource;
@@ -6,7 +6,7 @@ use opentelemetry_semantic_conventions as semconv;
 use serde::Deserialize;
 use tracing_subscriber::{prelude::*, EnvFilter};
 
-use config::prelude::*;
+use config::{prelude::*, Origin};
 use errors::prelude::*;
 
 use crate::layers::{logs, metrics, rpc::RPCLayer, traces};
@@ -36,7 +36,7 @@ pub enum Error {
     Internal(String),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Exporter {
     Console,
     Noop,
@@ -58,36 +58,53 @@ impl FromStr for Exporter {
     }
 }
 
+impl fmt::Display for Exporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Exporter::Console => "console",
+            Exporter::Noop => "noop",
+            Exporter::Otlp => "otlp",
+            Exporter::Stdout => "stdout",
+        })
+    }
+}
+
+config::from_str_serde!(Exporter);
+
 #[derive(Config, Debug, Deserialize)]
 pub struct Config {
-    #[config(default = "console")]
-    pub logs_exporter: String,
+    /// Logs exporter (console, noop, otlp or stdout).
+    #[config(default = Exporter::Console, profile(production = Exporter::Otlp))]
+    pub logs_exporter: Exporter,
 
+    /// Logs filter directives (e.g. `info,my_crate=debug`).
     #[config(default = "info")]
     pub logs_filter: String,
 
-    #[config(default = "noop")]
-    pub metrics_exporter: String,
+    /// Metrics exporter (noop, otlp or stdout).
+    #[config(default = Exporter::Noop, profile(production = Exporter::Otlp))]
+    pub metrics_exporter: Exporter,
 
+    /// Metrics filter directives.
     #[config(default = "info")]
     pub metrics_filter: String,
 
-    #[config(default = "noop")]
-    pub traces_exporter: String,
+    /// Traces exporter (noop, otlp or stdout).
+    #[config(default = Exporter::Noop, profile(production = Exporter::Otlp))]
+    pub traces_exporter: Exporter,
 
+    /// Traces filter directives.
     #[config(default = "info")]
     pub traces_filter: String,
 
-    #[config(default = "0")]
+    /// Ratio of traces to sample, between 0 and 1.
+    #[config(default = 0.0, range(min = 0.0, max = 1.0))]
     pub traces_ratio_sample: f64,
 }
 
 pub fn new(service_name: &'static str, service_version: &'static str) -> Result<(), Error> {
-    let config = Config::from_env("INSTRUMENTS").map_err(|v| Error::Configuration(v.to_string()))?;
-
-    let logs_exporter = config.logs_exporter.parse()?;
-    let metrics_exporter = config.metrics_exporter.parse()?;
-    let traces_exporter = config.traces_exporter.parse()?;
+    let (config, resolution) =
+        Config::resolve("INSTRUMENTS").map_err(|v| Error::Configuration(v.to_string()))?;
 
     let mut pairs = vec![
         KeyValue::new(semconv::resource::OTEL_SCOPE_NAME, SCOPE_NAME),
@@ -101,14 +118,14 @@ pub fn new(service_name: &'static str, service_version: &'static str) -> Result<
 
     let resource = Resource::new(pairs);
 
-    let logs_layer = logs::new_layer(resource.clone(), logs_exporter)?;
+    let logs_layer = logs::new_layer(resource.clone(), config.logs_exporter)?;
 
-    let metrics_layer = metrics::new_layer(resource.clone(), metrics_exporter)?;
+    let metrics_layer = metrics::new_layer(resource.clone(), config.metrics_exporter)?;
 
     let traces_layer = traces::new_layer(
-        &service_name,
+        service_name,
         resource,
-        traces_exporter,
+        config.traces_exporter,
         config.traces_ratio_sample,
     )?;
 
@@ -119,5 +136,78 @@ pub fn new(service_name: &'static str, service_version: &'static str) -> Result<
         .with(traces_layer.with_filter(EnvFilter::new(&config.traces_filter)))
         .init();
 
+    for field in resolution.fields {
+        // Logged again, the loader warnings being emitted before the subscriber is set up.
+        if let Origin::Env {
+            name,
+            deprecated: true,
+        } = &field.origin
+        {
+            crate::warn!(
+                config.key = field.key,
+                "environment variable {} is deprecated, use {} instead",
+                name,
+                field.env
+            );
+        }
+
+        crate::debug!(
+            config.key = field.key,
+            config.value = field.value,
+            config.origin = field.origin.to_string(),
+            "configuration value loaded"
+        );
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn load(vars: &[(&str, &str)]) -> Config {
+        let vars = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        Config::from_map("INSTRUMENTS", vars).unwrap()
+    }
+
+    #[test]
+    fn defaults_to_otlp_in_production() {
+        let config = load(&[("ENV", "production")]);
+
+        assert_eq!(config.logs_exporter, Exporter::Otlp);
+        assert_eq!(config.metrics_exporter, Exporter::Otlp);
+        assert_eq!(config.traces_exporter, Exporter::Otlp);
+        assert_eq!(config.logs_filter, "info");
+        assert_eq!(config.traces_ratio_sample, 0.0);
+
+        let config = load(&[]);
+
+        assert_eq!(config.logs_exporter, Exporter::Console);
+        assert_eq!(config.metrics_exporter, Exporter::Noop);
+        assert_eq!(config.traces_exporter, Exporter::Noop);
+    }
+
+    #[test]
+    fn parses_typed_fields() {
+        let config = load(&[
+            ("ENV", "production"),
+            ("INSTRUMENTS_LOGS_EXPORTER", "STDOUT"),
+            ("INSTRUMENTS_TRACES_FILTER", "debug"),
+            ("INSTRUMENTS_TRACES_RATIO_SAMPLE", "0.25"),
+        ]);
+
+        assert_eq!(config.logs_exporter, Exporter::Stdout);
+        assert_eq!(config.metrics_exporter, Exporter::Otlp);
+        assert_eq!(config.traces_filter, "debug");
+        assert_eq!(config.traces_ratio_sample, 0.25);
+
+        let vars = HashMap::from([("INSTRUMENTS_METRICS_EXPORTER".to_string(), "jaeger".to_string())]);
+        assert!(Config::from_map("INSTRUMENTS", vars).is_err());
+
+        let vars = HashMap::from([("INSTRUMENTS_TRACES_RATIO_SAMPLE".to_string(), "2".to_string())]);
+        assert!(Config::from_map("INSTRUMENTS", vars).is_err());
+    }
+}
@@ -7,6 +7,8 @@ use tower_http::{
 };
 use tracing::{Level, Span};
 
+use errors::Code;
+
 use crate::{
     counter, histogram,
     prelude::__internal_paste,
@@ -181,22 +183,6 @@ fn classification_from_headers(headers: &HeaderMap) -> (Option<&str>, Option<Str
 fn code_name(code: i32) -> Option<&'static str> {
     match code {
         0 => Some("ok"),
-        1 => Some("cancelled"),
-        2 => Some("unknown"),
-        3 => Some("invalid_argument"),
-        4 => Some("deadline_exceeded"),
-        5 => Some("not_found"),
-        6 => Some("already_exists"),
-        7 => Some("permission_denied"),
-        8 => Some("resource_exhausted"),
-        9 => Some("failed_precondition"),
-        10 => Some("aborted"),
-        11 => Some("out_of_range"),
-        12 => Some("unimplemented"),
-        13 => Some("internal"),
-        14 => Some("unavailable"),
-        15 => Some("data_loss"),
-        16 => Some("unauthenticated"),
-        _ => None,
+        code => Code::from_grpc(code).map(|v| v.name()),
     }
 }